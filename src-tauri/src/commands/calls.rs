@@ -0,0 +1,245 @@
+//! PostCLI Calls Module
+//!
+//! This module runs short-lived PostCLI invocations (provider listing, version,
+//! help) to completion without blocking the command thread. Every call has a
+//! timeout, after which PostCLI is killed, and can be cancelled from the frontend
+//! by the call id it was started with. A hung `-printProviders`, e.g. caused by a
+//! broken OpenCL ICD, therefore cannot freeze the application.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::State;
+
+/// Timeout of a call when the frontend does not specify one
+pub const DEFAULT_CALL_TIMEOUT_SECS: u64 = 60;
+
+/// Interval between checks of a running call for exit, timeout and cancellation
+const CALL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Output of a PostCLI call that ran to completion
+#[derive(Serialize)]
+pub struct CommandOutput {
+    stdout: String,
+    stderr: String,
+    /// Exit code, `None` if PostCLI was killed by a signal
+    exit_code: Option<i32>,
+    /// Whether PostCLI exited with code 0
+    success: bool,
+}
+
+/// Tauri-managed registry of the cancellation flags of running calls
+#[derive(Default)]
+pub struct CallRegistry {
+    calls: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl CallRegistry {
+    /// Registers a call and returns its cancellation flag
+    fn register(&self, call_id: &str) -> Result<Arc<AtomicBool>, String> {
+        let mut calls = self.calls.lock().unwrap();
+        if calls.contains_key(call_id) {
+            return Err(format!("A postcli call with id {} is already running", call_id));
+        }
+        let cancelled = Arc::new(AtomicBool::new(false));
+        calls.insert(call_id.to_string(), cancelled.clone());
+        Ok(cancelled)
+    }
+
+    fn unregister(&self, call_id: &str) {
+        self.calls.lock().unwrap().remove(call_id);
+    }
+
+    /// Flags a running call for cancellation
+    fn cancel(&self, call_id: &str) -> Result<(), String> {
+        let calls = self.calls.lock().unwrap();
+        let cancelled = calls
+            .get(call_id)
+            .ok_or_else(|| format!("No postcli call with id {} is running", call_id))?;
+        cancelled.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// Runs a command to completion in a blocking task
+///
+/// # Arguments
+///
+/// * `command` - Fully configured command; its output pipes are replaced
+/// * `call_id` - Optional id under which the call can be cancelled
+/// * `timeout` - Time after which the process is killed
+/// * `calls` - Registry holding the cancellation flags
+///
+/// # Returns
+///
+/// * `Ok(CommandOutput)` - Output and exit status if the process exited on its own
+/// * `Err(String)` - If the process could not be started, timed out or was cancelled
+pub async fn run_call(
+    mut command: Command,
+    call_id: Option<String>,
+    timeout: Duration,
+    calls: &CallRegistry,
+) -> Result<CommandOutput, String> {
+    let cancelled = match &call_id {
+        Some(id) => calls.register(id)?,
+        None => Arc::new(AtomicBool::new(false)),
+    };
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to execute postcli: {}", e))?;
+
+        let stdout = child.stdout.take().map(read_to_end);
+        let stderr = child.stderr.take().map(read_to_end);
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child
+                .try_wait()
+                .map_err(|e| format!("Failed to wait for postcli: {}", e))?
+            {
+                break Ok(status);
+            }
+            if cancelled.load(Ordering::SeqCst) {
+                break Err("postcli call was cancelled".to_string());
+            }
+            if started.elapsed() >= timeout {
+                break Err(format!("postcli did not finish within {}s", timeout.as_secs()));
+            }
+            thread::sleep(CALL_POLL_INTERVAL);
+        };
+
+        if status.is_err() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        let status = status?;
+
+        let stdout = stdout.and_then(|r| r.join().ok()).unwrap_or_default();
+        let stderr = stderr.and_then(|r| r.join().ok()).unwrap_or_default();
+
+        // The output itself is returned to the caller
+        println!(
+            "postcli call exited with {} ({} bytes of stdout, {} bytes of stderr)",
+            status,
+            stdout.len(),
+            stderr.len()
+        );
+
+        Ok(CommandOutput {
+            stdout,
+            stderr,
+            exit_code: status.code(),
+            success: status.success(),
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to run postcli: {}", e))
+    .and_then(|result| result);
+
+    if let Some(id) = &call_id {
+        calls.unregister(id);
+    }
+    result
+}
+
+/// Reads a pipe to the end in a background thread
+///
+/// Reading both pipes concurrently keeps PostCLI from blocking on a full pipe.
+fn read_to_end<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        String::from_utf8_lossy(&buffer).to_string()
+    })
+}
+
+/// Cancels a running PostCLI call
+///
+/// The call's process is killed and the call fails with a cancellation error.
+///
+/// # Arguments
+///
+/// * `call_id` - Id the call was started with
+///
+/// # Returns
+///
+/// * `Ok(())` - If the call was found and flagged for cancellation
+/// * `Err(String)` - If no call with this id is running
+#[tauri::command]
+pub fn cancel_postcli_call(call_id: String, calls: State<'_, CallRegistry>) -> Result<(), String> {
+    calls.cancel(&call_id)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    fn run(
+        script: &str,
+        call_id: Option<&str>,
+        timeout: Duration,
+        calls: &CallRegistry,
+    ) -> Result<CommandOutput, String> {
+        tauri::async_runtime::block_on(run_call(shell(script), call_id.map(String::from), timeout, calls))
+    }
+
+    #[test]
+    fn maps_exit_status() {
+        let calls = CallRegistry::default();
+        let output = run("echo out; echo err >&2", None, Duration::from_secs(10), &calls).unwrap();
+        assert_eq!((output.stdout.as_str(), output.stderr.as_str()), ("out\n", "err\n"));
+        assert_eq!(output.exit_code, Some(0));
+        assert!(output.success);
+
+        let output = run("exit 3", None, Duration::from_secs(10), &calls).unwrap();
+        assert_eq!(output.exit_code, Some(3));
+        assert!(!output.success);
+
+        let output = run("kill -9 $$", None, Duration::from_secs(10), &calls).unwrap();
+        assert_eq!(output.exit_code, None);
+        assert!(!output.success);
+    }
+
+    #[test]
+    fn kills_call_after_timeout() {
+        let calls = CallRegistry::default();
+        let started = Instant::now();
+        let error = run("sleep 10", None, Duration::from_millis(200), &calls).err().unwrap();
+        assert!(error.contains("did not finish"), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn cancels_call_by_id() {
+        let calls = CallRegistry::default();
+        assert!(calls.cancel("call").is_err());
+        let started = Instant::now();
+        let error = thread::scope(|scope| {
+            scope.spawn(|| {
+                while calls.cancel("call").is_err() {
+                    thread::sleep(Duration::from_millis(10));
+                }
+            });
+            run("sleep 10", Some("call"), Duration::from_secs(30), &calls).err().unwrap()
+        });
+        assert!(error.contains("cancelled"), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        // The id is released once the call finished
+        assert!(calls.cancel("call").is_err());
+    }
+}
@@ -0,0 +1,358 @@
+//! PostCLI Configuration Module
+//!
+//! This module defines the typed configuration of a PostCLI invocation. The
+//! frontend sends a [`PostCliConfig`] instead of raw arguments; the backend
+//! validates it and renders it to the command-line arguments understood by PostCLI,
+//! so that no unchecked argument vector reaches the executable.
+//!
+//! Validation collects every problem at once and reports it as a structured
+//! [`PostCliError`], allowing the frontend to highlight the offending fields.
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use super::pos_data::{PosLayout, BYTES_PER_LABEL};
+
+/// Length of the commitment ATX ID and node ID in hex characters (32 bytes)
+const HEX_ID_LENGTH: usize = 64;
+
+/// Minimum number of space units accepted by the network
+pub const MIN_NUM_UNITS: u32 = 4;
+
+/// Bounds for `-maxFileSize` in bytes (1 MiB to 8 GiB)
+const MIN_MAX_FILE_SIZE: u64 = 1024 * 1024;
+const MAX_MAX_FILE_SIZE: u64 = 8 * 1024 * 1024 * 1024;
+
+/// Log levels accepted by PostCLI's `-logLevel`
+const LOG_LEVELS: [&str; 7] = ["debug", "info", "warn", "error", "dpanic", "panic", "fatal"];
+
+/// Configuration of a PostCLI invocation
+///
+/// All fields are optional in JSON; missing ones take their default value and
+/// are reported by [`PostCliConfig::validate`] when they are required.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostCliConfig {
+    /// Only list the available OpenCL providers, all other fields are ignored
+    pub print_providers: bool,
+    /// Directory the PoS data is written to, PostCLI's `~/post/data` if omitted
+    pub datadir: Option<String>,
+    /// Number of space units to initialize
+    pub num_units: u32,
+    /// Index of the OpenCL provider to use
+    pub provider: u32,
+    /// Commitment ATX ID as a hex string
+    pub commitment_atx_id: String,
+    /// Node identity (public key) as a hex string, generated by PostCLI if omitted
+    pub id: Option<String>,
+    /// Maximum size of a single data file in bytes
+    pub max_file_size: Option<u64>,
+    /// Number of labels per space unit, the network default if omitted
+    pub labels_per_unit: Option<u64>,
+    /// First file to initialize
+    pub from_file: Option<u32>,
+    /// Last file to initialize, inclusive
+    pub to_file: Option<u32>,
+    /// Generate a proof after the initialization to verify the data
+    pub genproof: bool,
+    /// PostCLI log level
+    pub log_level: Option<String>,
+}
+
+/// Problem with a single configuration field
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    /// Name of the offending field as sent by the frontend
+    pub field: &'static str,
+    pub message: String,
+}
+
+/// Error returned by commands taking a [`PostCliConfig`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PostCliError {
+    /// The configuration was rejected before PostCLI was started
+    Validation { errors: Vec<FieldError> },
+    /// PostCLI could not be started or run
+    Failed { message: String },
+}
+
+impl From<String> for PostCliError {
+    fn from(message: String) -> Self {
+        PostCliError::Failed { message }
+    }
+}
+
+impl From<&str> for PostCliError {
+    fn from(message: &str) -> Self {
+        PostCliError::Failed { message: message.to_string() }
+    }
+}
+
+impl PostCliConfig {
+    /// Checks the configuration before it is handed to PostCLI
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the configuration can be rendered to arguments
+    /// * `Err(PostCliError::Validation)` - With one entry per invalid field
+    pub fn validate(&self) -> Result<(), PostCliError> {
+        if self.print_providers {
+            return Ok(());
+        }
+
+        let mut errors = Vec::new();
+        let mut error = |field: &'static str, message: String| errors.push(FieldError { field, message });
+
+        if !is_hex(&self.commitment_atx_id, HEX_ID_LENGTH) {
+            error(
+                "commitment_atx_id",
+                format!("must be a {}-character hexadecimal string", HEX_ID_LENGTH),
+            );
+        }
+        if let Some(id) = &self.id {
+            if !is_hex(id, HEX_ID_LENGTH) {
+                error("id", format!("must be a {}-character hexadecimal string", HEX_ID_LENGTH));
+            }
+        }
+        if self.num_units < MIN_NUM_UNITS {
+            error("num_units", format!("must be at least {}", MIN_NUM_UNITS));
+        }
+        if let Some(size) = self.max_file_size {
+            if !(MIN_MAX_FILE_SIZE..=MAX_MAX_FILE_SIZE).contains(&size) {
+                error(
+                    "max_file_size",
+                    format!("must be between {} and {} bytes", MIN_MAX_FILE_SIZE, MAX_MAX_FILE_SIZE),
+                );
+            } else if size % BYTES_PER_LABEL != 0 {
+                error("max_file_size", format!("must be a multiple of {} bytes", BYTES_PER_LABEL));
+            }
+        }
+        if self.labels_per_unit == Some(0) {
+            error("labels_per_unit", "must be greater than 0".to_string());
+        }
+        if let Some(datadir) = &self.datadir {
+            if datadir.trim().is_empty() {
+                error("datadir", "must not be empty".to_string());
+            } else if !Path::new(datadir).is_dir() {
+                error("datadir", format!("directory {} does not exist", datadir));
+            }
+        }
+        if let Some(level) = &self.log_level {
+            if !LOG_LEVELS.contains(&level.as_str()) {
+                error("log_level", format!("must be one of {}", LOG_LEVELS.join(", ")));
+            }
+        }
+
+        let num_files = PosLayout::from_args(&self.to_args()).num_files();
+        if let Some(from_file) = self.from_file {
+            if from_file >= num_files {
+                error("from_file", format!("must be lower than the number of files ({})", num_files));
+            }
+        }
+        if let Some(to_file) = self.to_file {
+            if to_file >= num_files {
+                error("to_file", format!("must be lower than the number of files ({})", num_files));
+            } else if self.from_file.is_some_and(|from_file| from_file > to_file) {
+                error("to_file", "must not be lower than from_file".to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(PostCliError::Validation { errors })
+        }
+    }
+
+    /// Checks that the configuration describes an initialization
+    ///
+    /// Used by commands that manage initializations, which cannot run `-printProviders`.
+    pub fn validate_init(&self) -> Result<(), PostCliError> {
+        if self.print_providers {
+            return Err(PostCliError::Validation {
+                errors: vec![FieldError {
+                    field: "print_providers",
+                    message: "must not be set for an initialization".to_string(),
+                }],
+            });
+        }
+        self.validate()
+    }
+
+    /// Renders the configuration to PostCLI command-line arguments
+    pub fn to_args(&self) -> Vec<String> {
+        if self.print_providers {
+            return vec!["-printProviders".to_string()];
+        }
+
+        let mut args = vec![
+            format!("-provider={}", self.provider),
+            format!("-numUnits={}", self.num_units),
+            format!("-commitmentAtxId={}", self.commitment_atx_id),
+        ];
+        if let Some(id) = &self.id {
+            args.push(format!("-id={}", id));
+        }
+        if let Some(datadir) = &self.datadir {
+            args.push(format!("-datadir={}", datadir));
+        }
+        if let Some(max_file_size) = self.max_file_size {
+            args.push(format!("-maxFileSize={}", max_file_size));
+        }
+        if let Some(labels_per_unit) = self.labels_per_unit {
+            args.push(format!("-labelsPerUnit={}", labels_per_unit));
+        }
+        if let Some(from_file) = self.from_file {
+            args.push(format!("-fromFile={}", from_file));
+        }
+        if let Some(to_file) = self.to_file {
+            args.push(format!("-toFile={}", to_file));
+        }
+        if let Some(level) = &self.log_level {
+            args.push(format!("-logLevel={}", level));
+        }
+        if self.genproof {
+            args.push("-genproof".to_string());
+        }
+        args.push("-yes".to_string());
+        args
+    }
+}
+
+/// Checks that a string consists of exactly `length` hexadecimal characters
+fn is_hex(value: &str, length: usize) -> bool {
+    value.len() == length && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_config() -> PostCliConfig {
+        PostCliConfig {
+            num_units: 4,
+            provider: 1,
+            commitment_atx_id: "ab".repeat(32),
+            ..PostCliConfig::default()
+        }
+    }
+
+    fn invalid_fields(config: &PostCliConfig) -> Vec<&'static str> {
+        match config.validate() {
+            Err(PostCliError::Validation { errors }) => errors.iter().map(|e| e.field).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn accepts_valid_config() {
+        assert!(valid_config().validate().is_ok());
+    }
+
+    #[test]
+    fn renders_args() {
+        let config = PostCliConfig {
+            id: Some("cd".repeat(32)),
+            datadir: Some("/data/post".to_string()),
+            max_file_size: Some(1 << 30),
+            from_file: Some(2),
+            to_file: Some(5),
+            genproof: true,
+            log_level: Some("debug".to_string()),
+            ..valid_config()
+        };
+        assert_eq!(
+            config.to_args(),
+            vec![
+                "-provider=1".to_string(),
+                "-numUnits=4".to_string(),
+                format!("-commitmentAtxId={}", "ab".repeat(32)),
+                format!("-id={}", "cd".repeat(32)),
+                "-datadir=/data/post".to_string(),
+                "-maxFileSize=1073741824".to_string(),
+                "-fromFile=2".to_string(),
+                "-toFile=5".to_string(),
+                "-logLevel=debug".to_string(),
+                "-genproof".to_string(),
+                "-yes".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn print_providers_ignores_other_fields() {
+        let config = PostCliConfig {
+            print_providers: true,
+            ..PostCliConfig::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.to_args(), vec!["-printProviders".to_string()]);
+    }
+
+    #[test]
+    fn rejects_invalid_hex_ids() {
+        let config = PostCliConfig {
+            commitment_atx_id: "zz".repeat(32),
+            id: Some("ab".repeat(16)),
+            ..valid_config()
+        };
+        assert_eq!(invalid_fields(&config), vec!["commitment_atx_id", "id"]);
+    }
+
+    #[test]
+    fn rejects_too_few_units() {
+        let config = PostCliConfig {
+            num_units: 1,
+            ..valid_config()
+        };
+        assert_eq!(invalid_fields(&config), vec!["num_units"]);
+    }
+
+    #[test]
+    fn rejects_max_file_size_out_of_bounds() {
+        for size in [1024, 16 * 1024 * 1024 * 1024, (1 << 30) + 1] {
+            let config = PostCliConfig {
+                max_file_size: Some(size),
+                ..valid_config()
+            };
+            assert_eq!(invalid_fields(&config), vec!["max_file_size"], "size {}", size);
+        }
+    }
+
+    #[test]
+    fn rejects_missing_datadir() {
+        let config = PostCliConfig {
+            datadir: Some("/nonexistent/post/data".to_string()),
+            ..valid_config()
+        };
+        assert_eq!(invalid_fields(&config), vec!["datadir"]);
+    }
+
+    #[test]
+    fn rejects_invalid_file_range() {
+        // 4 units of 64 GiB in 4 GiB files make 64 files
+        let config = PostCliConfig {
+            from_file: Some(10),
+            to_file: Some(5),
+            ..valid_config()
+        };
+        assert_eq!(invalid_fields(&config), vec!["to_file"]);
+
+        let config = PostCliConfig {
+            from_file: Some(64),
+            to_file: Some(64),
+            ..valid_config()
+        };
+        assert_eq!(invalid_fields(&config), vec!["from_file", "to_file"]);
+    }
+
+    #[test]
+    fn rejects_unknown_log_level() {
+        let config = PostCliConfig {
+            log_level: Some("verbose".to_string()),
+            ..valid_config()
+        };
+        assert_eq!(invalid_fields(&config), vec!["log_level"]);
+    }
+}
@@ -8,3 +8,41 @@ pub fn get_cpu_cores() -> usize {
     // Get the number of logical CPUs (including virtual cores)
     num_cpus::get()
 }
+
+/// Returns the model name of the CPU, if the platform reports one
+#[cfg(target_os = "linux")]
+pub fn cpu_model() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    // x86 reports "model name", some ARM kernels only "Hardware" or "Processor"
+    ["model name", "Hardware", "Processor"].iter().find_map(|key| {
+        cpuinfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            if name.trim() == *key && !value.trim().is_empty() {
+                Some(value.trim().to_string())
+            } else {
+                None
+            }
+        })
+    })
+}
+
+/// Returns the model name of the CPU, if the platform reports one
+#[cfg(target_os = "macos")]
+pub fn cpu_model() -> Option<String> {
+    let output = std::process::Command::new("sysctl")
+        .args(["-n", "machdep.cpu.brand_string"])
+        .output()
+        .ok()?;
+    let model = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !model.is_empty() {
+        Some(model)
+    } else {
+        None
+    }
+}
+
+/// Returns the model name of the CPU, if the platform reports one
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn cpu_model() -> Option<String> {
+    std::env::var("PROCESSOR_IDENTIFIER").ok()
+}
@@ -0,0 +1,393 @@
+//! Disk Module
+//!
+//! This module queries the filesystem that holds a given directory. Unlike
+//! `sys_info::disk_info`, which only describes the root filesystem, the queries
+//! follow the path, so a PoS directory on a separate drive is measured correctly.
+//!
+//! Besides the free space, it detects the filesystem type, mount point and
+//! device of a directory and whether the storage is removable or network-backed,
+//! and derives warnings about filesystems unsuited for PoS data.
+//!
+//! On Linux the mount is looked up in `/proc/self/mountinfo` and the block
+//! device in `/sys/dev/block`, which links to the device's `/sys/block` entry.
+
+use std::path::Path;
+use serde::Serialize;
+
+#[cfg(target_os = "macos")]
+use std::ffi::CStr;
+#[cfg(unix)]
+use std::ffi::CString;
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(any(target_os = "linux", test))]
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+#[cfg(windows)]
+use std::os::windows::ffi::OsStrExt;
+#[cfg(windows)]
+use winapi::shared::minwindef::MAX_PATH;
+#[cfg(windows)]
+use winapi::shared::ntdef::ULARGE_INTEGER;
+#[cfg(windows)]
+use winapi::um::fileapi::{GetDiskFreeSpaceExW, GetDriveTypeW, GetVolumeInformationW, GetVolumePathNameW};
+#[cfg(windows)]
+use winapi::um::winbase::{DRIVE_REMOTE, DRIVE_REMOVABLE};
+
+/// Largest file a FAT32 filesystem can hold (4 GiB - 1 byte)
+const FAT32_MAX_FILE_SIZE: u64 = 4 * 1024 * 1024 * 1024 - 1;
+
+/// Filesystem types backed by a network share
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const NETWORK_FS_TYPES: [&str; 12] = [
+    "nfs", "nfs4", "cifs", "smb3", "smbfs", "9p", "afs", "ceph", "glusterfs", "fuse.sshfs", "davfs", "webdav",
+];
+
+/// Filesystem holding a directory
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FilesystemInfo {
+    /// Filesystem type as reported by the OS, e.g. `ext4`, `vfat`, `apfs` or `NTFS`
+    pub fs_type: String,
+    /// Directory (or volume on Windows) the filesystem is mounted at
+    pub mount_point: Option<String>,
+    /// Device or share the filesystem is mounted from
+    pub device: Option<String>,
+    /// Whether the storage is a removable drive, `None` if unknown
+    pub removable: Option<bool>,
+    /// Whether the filesystem is backed by a network share
+    pub network: bool,
+}
+
+/// Returns the space available to the current user on the filesystem holding `path`
+///
+/// # Returns
+///
+/// * `Ok(u64)` - Available space in bytes
+/// * `Err(String)` - If the filesystem cannot be queried
+#[cfg(unix)]
+pub fn available_space(path: &Path) -> Result<u64, String> {
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| format!("Invalid path {}: {}", path.display(), e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(format!(
+            "Failed to query filesystem of {}: {}",
+            path.display(),
+            std::io::Error::last_os_error()
+        ));
+    }
+    // Blocks available to unprivileged users, excluding the reserved ones.
+    // The field types differ between platforms, hence the casts.
+    #[allow(clippy::unnecessary_cast)]
+    let available = stat.f_bavail as u64 * stat.f_frsize as u64;
+    Ok(available)
+}
+
+/// Returns the space available to the current user on the filesystem holding `path`
+///
+/// # Returns
+///
+/// * `Ok(u64)` - Available space in bytes
+/// * `Err(String)` - If the filesystem cannot be queried
+#[cfg(windows)]
+pub fn available_space(path: &Path) -> Result<u64, String> {
+    let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    unsafe {
+        let mut available: ULARGE_INTEGER = std::mem::zeroed();
+        let result = GetDiskFreeSpaceExW(
+            wide_path.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        );
+        if result == 0 {
+            return Err(format!(
+                "Failed to query filesystem of {}: {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(*available.QuadPart())
+    }
+}
+
+/// Detects the filesystem holding `path`
+///
+/// # Returns
+///
+/// * `Ok(FilesystemInfo)` - Type, mount and storage kind of the filesystem
+/// * `Err(String)` - If the filesystem cannot be determined
+#[cfg(target_os = "linux")]
+pub fn filesystem_info(path: &Path) -> Result<FilesystemInfo, String> {
+    let path = fs::canonicalize(path).map_err(|e| format!("Failed to resolve {}: {}", path.display(), e))?;
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo")
+        .map_err(|e| format!("Failed to read mount table: {}", e))?;
+    let mount = find_mount(&mountinfo, &path)
+        .ok_or_else(|| format!("No mount found for {}", path.display()))?;
+
+    let network = is_network_fs(&mount.fs_type);
+    Ok(FilesystemInfo {
+        removable: if network { Some(false) } else { block_device_removable(&mount.major_minor) },
+        network,
+        fs_type: mount.fs_type,
+        mount_point: Some(mount.mount_point.display().to_string()),
+        device: Some(mount.source),
+    })
+}
+
+/// Detects the filesystem holding `path`
+///
+/// # Returns
+///
+/// * `Ok(FilesystemInfo)` - Type, mount and storage kind of the filesystem
+/// * `Err(String)` - If the filesystem cannot be determined
+#[cfg(target_os = "macos")]
+pub fn filesystem_info(path: &Path) -> Result<FilesystemInfo, String> {
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| format!("Invalid path {}: {}", path.display(), e))?;
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(format!(
+            "Failed to query filesystem of {}: {}",
+            path.display(),
+            std::io::Error::last_os_error()
+        ));
+    }
+    let text = |chars: &[libc::c_char]| unsafe { CStr::from_ptr(chars.as_ptr()) }.to_string_lossy().to_string();
+    let fs_type = text(&stat.f_fstypename);
+    Ok(FilesystemInfo {
+        network: stat.f_flags & libc::MNT_LOCAL as u32 == 0 || is_network_fs(&fs_type),
+        fs_type,
+        mount_point: Some(text(&stat.f_mntonname)),
+        device: Some(text(&stat.f_mntfromname)),
+        removable: None,
+    })
+}
+
+/// Detects the filesystem holding `path`
+///
+/// # Returns
+///
+/// * `Ok(FilesystemInfo)` - Type, mount and storage kind of the filesystem
+/// * `Err(String)` - If the filesystem cannot be determined
+#[cfg(windows)]
+pub fn filesystem_info(path: &Path) -> Result<FilesystemInfo, String> {
+    let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut volume = [0u16; MAX_PATH + 1];
+    let mut fs_name = [0u16; MAX_PATH + 1];
+    let drive_type = unsafe {
+        if GetVolumePathNameW(wide_path.as_ptr(), volume.as_mut_ptr(), volume.len() as u32) == 0 {
+            return Err(format!(
+                "Failed to find volume of {}: {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            ));
+        }
+        if GetVolumeInformationW(
+            volume.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            fs_name.as_mut_ptr(),
+            fs_name.len() as u32,
+        ) == 0
+        {
+            return Err(format!(
+                "Failed to query filesystem of {}: {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            ));
+        }
+        GetDriveTypeW(volume.as_ptr())
+    };
+    let text = |chars: &[u16]| {
+        let len = chars.iter().position(|c| *c == 0).unwrap_or(chars.len());
+        String::from_utf16_lossy(&chars[..len])
+    };
+    Ok(FilesystemInfo {
+        fs_type: text(&fs_name),
+        mount_point: Some(text(&volume)),
+        device: None,
+        removable: Some(drive_type == DRIVE_REMOVABLE),
+        network: drive_type == DRIVE_REMOTE,
+    })
+}
+
+/// Detects the filesystem holding `path`
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
+pub fn filesystem_info(_path: &Path) -> Result<FilesystemInfo, String> {
+    Err("Filesystem detection is not supported on this platform".to_string())
+}
+
+/// Warnings about storing PoS data on a filesystem
+///
+/// # Arguments
+///
+/// * `info` - Filesystem holding the data directory
+/// * `max_file_size` - Planned maximum size of a data file in bytes
+pub fn filesystem_warnings(info: &FilesystemInfo, max_file_size: u64) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let fs_type = info.fs_type.to_lowercase();
+
+    if ["vfat", "msdos", "fat", "fat32"].contains(&fs_type.as_str()) && max_file_size > FAT32_MAX_FILE_SIZE {
+        warnings.push("FAT32 cannot hold files larger than 4 GiB; maxFileSize must be reduced".to_string());
+    }
+    if fs_type == "exfat" {
+        warnings.push(
+            "exFAT has no journal; data files may be corrupted if the drive is disconnected during initialization"
+                .to_string(),
+        );
+    }
+    if ["tmpfs", "ramfs"].contains(&fs_type.as_str()) {
+        warnings.push(format!("{} is held in memory; the data will be lost on reboot", fs_type));
+    }
+    if info.network {
+        warnings.push("Network filesystem will be slow for proving".to_string());
+    }
+    if info.removable == Some(true) {
+        warnings.push("Removable drive must stay connected during initialization and proving".to_string());
+    }
+    warnings
+}
+
+/// Checks whether a filesystem type is backed by a network share
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn is_network_fs(fs_type: &str) -> bool {
+    NETWORK_FS_TYPES.contains(&fs_type.to_lowercase().as_str())
+}
+
+/// Mount entry of `/proc/self/mountinfo`
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, PartialEq)]
+struct MountEntry {
+    /// `major:minor` of the device
+    major_minor: String,
+    mount_point: PathBuf,
+    fs_type: String,
+    source: String,
+}
+
+/// Finds the mount holding `path` in the content of `/proc/self/mountinfo`
+///
+/// The deepest mount point containing the path wins; among equal mount points
+/// the last one, which is mounted over the others.
+#[cfg(any(target_os = "linux", test))]
+fn find_mount(mountinfo: &str, path: &Path) -> Option<MountEntry> {
+    let mut found: Option<MountEntry> = None;
+    for line in mountinfo.lines() {
+        // 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+        let (mount, filesystem) = match line.split_once(" - ") {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let mount: Vec<&str> = mount.split(' ').collect();
+        let filesystem: Vec<&str> = filesystem.split(' ').collect();
+        if mount.len() < 5 || filesystem.len() < 2 {
+            continue;
+        }
+        let mount_point = PathBuf::from(unescape_mount_field(mount[4]));
+        if !path.starts_with(&mount_point) {
+            continue;
+        }
+        let deeper = found
+            .as_ref()
+            .is_none_or(|f| mount_point.components().count() >= f.mount_point.components().count());
+        if deeper {
+            found = Some(MountEntry {
+                major_minor: mount[2].to_string(),
+                mount_point,
+                fs_type: filesystem[0].to_string(),
+                source: unescape_mount_field(filesystem[1]),
+            });
+        }
+    }
+    found
+}
+
+/// Decodes the octal escapes (`\040` for a space) used in mount table fields
+#[cfg(any(target_os = "linux", test))]
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = bytes.get(i + 1..i + 4).filter(|_| bytes[i] == b'\\');
+        match escape.and_then(|digits| u8::from_str_radix(std::str::from_utf8(digits).ok()?, 8).ok()) {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 4;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Checks whether the block device with the given `major:minor` is removable
+///
+/// Partitions inherit the flag of their disk. USB drives are treated as removable
+/// even if they report otherwise, as external USB disks usually do.
+#[cfg(target_os = "linux")]
+fn block_device_removable(major_minor: &str) -> Option<bool> {
+    let device = fs::canonicalize(Path::new("/sys/dev/block").join(major_minor)).ok()?;
+    let disk = if device.join("partition").exists() {
+        device.parent()?.to_path_buf()
+    } else {
+        device.clone()
+    };
+    let removable = fs::read_to_string(disk.join("removable")).ok()?.trim() == "1";
+    Some(removable || device.components().any(|c| c.as_os_str().to_string_lossy().starts_with("usb")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTINFO: &str = "\
+22 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw
+25 22 0:21 / /tmp rw,nosuid shared:2 - tmpfs tmpfs rw
+40 22 8:17 / /media/user/USB\\040DRIVE rw,nosuid shared:3 - vfat /dev/sdb1 rw,fmask=0022
+41 22 0:45 / /mnt/share rw,relatime shared:4 - nfs4 server:/export rw,vers=4.2
+";
+
+    #[test]
+    fn finds_deepest_mount() {
+        let mount = find_mount(MOUNTINFO, Path::new("/media/user/USB DRIVE/post")).unwrap();
+        assert_eq!(mount.fs_type, "vfat");
+        assert_eq!(mount.major_minor, "8:17");
+        assert_eq!(mount.mount_point, Path::new("/media/user/USB DRIVE"));
+        assert_eq!(mount.source, "/dev/sdb1");
+
+        let mount = find_mount(MOUNTINFO, Path::new("/home/user/post/data")).unwrap();
+        assert_eq!(mount.fs_type, "ext4");
+
+        // Mount points match whole path components only
+        let mount = find_mount(MOUNTINFO, Path::new("/tmpdata")).unwrap();
+        assert_eq!(mount.fs_type, "ext4");
+    }
+
+    #[test]
+    fn warns_about_unsuited_filesystems() {
+        let info = |fs_type: &str, network: bool| FilesystemInfo {
+            fs_type: fs_type.to_string(),
+            network,
+            ..FilesystemInfo::default()
+        };
+        let gib = 1024 * 1024 * 1024;
+
+        assert_eq!(filesystem_warnings(&info("vfat", false), 4 * gib).len(), 1);
+        assert!(filesystem_warnings(&info("vfat", false), 2 * gib).is_empty());
+        assert_eq!(filesystem_warnings(&info("tmpfs", false), 4 * gib).len(), 1);
+        assert_eq!(
+            filesystem_warnings(&info("nfs4", true), 4 * gib),
+            vec!["Network filesystem will be slow for proving".to_string()]
+        );
+        assert!(filesystem_warnings(&info("ext4", false), 4 * gib).is_empty());
+    }
+}
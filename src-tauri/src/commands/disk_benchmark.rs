@@ -0,0 +1,518 @@
+//! Disk Benchmark Module
+//!
+//! This module measures the throughput of the disk holding a PoS data directory,
+//! so the application can tell whether the disk rather than the provider limits
+//! an initialization, and whether proving, which reads the whole PoS data set,
+//! finishes in time.
+//!
+//! I/O bypasses the page cache where possible: `O_DIRECT` on Linux, `F_NOCACHE`
+//! on macOS and unbuffered I/O on Windows. Filesystems that do not support it,
+//! such as tmpfs, fall back to buffered I/O, with the data flushed to disk before
+//! the measurement ends.
+//!
+//! # Events
+//!
+//! Emits 'disk-benchmark-progress' events with a [`DiskBenchmarkProgress`] payload.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::Manager;
+
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+#[cfg(target_os = "linux")]
+use std::os::unix::fs::OpenOptionsExt;
+#[cfg(windows)]
+use std::os::windows::fs::OpenOptionsExt;
+#[cfg(windows)]
+use winapi::um::winbase::{FILE_FLAG_NO_BUFFERING, FILE_FLAG_WRITE_THROUGH};
+
+use super::disk;
+use super::pos_data::list_data_files;
+use super::pos_metadata::PostMetadata;
+
+/// Amount of data written when the frontend does not specify it (1 GiB)
+pub const DEFAULT_WRITE_SIZE_MIB: u64 = 1024;
+
+/// Amount of PoS data read when the frontend does not specify it (2 GiB)
+pub const DEFAULT_READ_SAMPLE_MIB: u64 = 2048;
+
+/// Size of a single write, large enough for sequential throughput
+const BLOCK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Buffer alignment required by uncached I/O (the largest common sector size)
+const ALIGNMENT: usize = 4096;
+
+/// Interval between two progress events
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+const MIB: u64 = 1024 * 1024;
+
+/// Payload of the 'disk-benchmark-progress' event
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskBenchmarkProgress {
+    /// Kind of the running benchmark: `write` or `read`
+    pub kind: &'static str,
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+    /// Average throughput so far in MiB/s
+    pub speed_mib_s: f64,
+}
+
+/// Result of [`benchmark_directory_write`]
+#[derive(Debug, Clone, Serialize)]
+pub struct WriteBenchmarkResult {
+    pub bytes_written: u64,
+    /// Time of the writes including the final flush in seconds
+    pub duration_s: f64,
+    /// Sequential write throughput in MiB/s
+    pub speed_mib_s: f64,
+    /// Whether the page cache was bypassed; if not, the result includes flushing the cache
+    pub direct_io: bool,
+}
+
+/// Result of [`benchmark_directory_read`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadBenchmarkResult {
+    pub bytes_read: u64,
+    /// Number of data files a sample was read from
+    pub files_sampled: u32,
+    pub duration_s: f64,
+    /// Sustained read throughput in MiB/s
+    pub speed_mib_s: f64,
+    /// Whether the page cache was bypassed or evicted before reading
+    pub direct_io: bool,
+    /// Size of the PoS data set in bytes, from the metadata if present
+    pub data_size: u64,
+    /// Projected duration of one proving pass over the whole data set in seconds
+    pub projected_pass_s: f64,
+}
+
+/// Heap buffer whose usable part starts at an [`ALIGNMENT`] boundary
+struct AlignedBuffer {
+    data: Vec<u8>,
+    offset: usize,
+    len: usize,
+}
+
+impl AlignedBuffer {
+    fn new(len: usize) -> AlignedBuffer {
+        let data = vec![0u8; len + ALIGNMENT];
+        let offset = data.as_ptr().align_offset(ALIGNMENT);
+        AlignedBuffer { data, offset, len }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.data[self.offset..self.offset + self.len]
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.data[self.offset..self.offset + self.len]
+    }
+}
+
+/// Opens a file bypassing the page cache where the platform and filesystem allow it
+///
+/// # Returns
+///
+/// * `Ok((File, bool))` - The file and whether the page cache is bypassed
+/// * `Err(io::Error)` - If the file cannot be opened at all
+fn open_uncached(path: &Path, options: &OpenOptions) -> io::Result<(File, bool)> {
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(file) = options.clone().custom_flags(libc::O_DIRECT).open(path) {
+            return Ok((file, true));
+        }
+    }
+    #[cfg(windows)]
+    {
+        if let Ok(file) = options
+            .clone()
+            .custom_flags(FILE_FLAG_NO_BUFFERING | FILE_FLAG_WRITE_THROUGH)
+            .open(path)
+        {
+            return Ok((file, true));
+        }
+    }
+
+    let file = options.open(path)?;
+    #[cfg(target_os = "macos")]
+    {
+        if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1) } == 0 {
+            return Ok((file, true));
+        }
+    }
+    Ok((file, false))
+}
+
+/// Drops the cached pages of a file opened without bypassing the cache
+///
+/// # Returns
+///
+/// * `bool` - Whether the pages were dropped, so reads go to the disk
+#[cfg(target_os = "linux")]
+fn evict_cached(file: &File) -> bool {
+    unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) == 0 }
+}
+
+/// Drops the cached pages of a file opened without bypassing the cache
+#[cfg(not(target_os = "linux"))]
+fn evict_cached(_file: &File) -> bool {
+    false
+}
+
+/// Fills a buffer with pseudo-random bytes, so compressing filesystems cannot shrink the writes
+fn fill_random(buffer: &mut [u8]) {
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    for chunk in buffer.chunks_mut(8) {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let bytes = state.to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+/// Converts bytes and elapsed time to MiB/s
+fn speed_mib_s(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 {
+        bytes as f64 / MIB as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+/// Emits a 'disk-benchmark-progress' event
+fn emit_progress(app: &tauri::AppHandle, progress: DiskBenchmarkProgress) {
+    if let Err(e) = app.emit_all("disk-benchmark-progress", progress) {
+        eprintln!("Failed to emit disk-benchmark-progress event: {}", e);
+    }
+}
+
+/// Measures the sequential write throughput of the disk holding a directory
+///
+/// A temporary file is written with large sequential writes and removed
+/// afterwards, also if the benchmark fails.
+///
+/// # Arguments
+///
+/// * `app` - Tauri application handle used to emit progress events
+/// * `dir` - Directory to benchmark, usually the PoS data directory
+/// * `size_mib` - Amount of data to write in MiB, 1 GiB if omitted
+///
+/// # Returns
+///
+/// * `Ok(WriteBenchmarkResult)` - Throughput of the writes
+/// * `Err(String)` - If the size is too large, the directory lacks space or the file cannot be written
+///
+/// # Events
+///
+/// * 'disk-benchmark-progress' - Every 250 ms while writing
+///
+/// # Example
+///
+/// ```rust
+/// let result = benchmark_directory_write(app, "/path/to/post/data".to_string(), Some(2048)).await?;
+/// println!("Disk writes {:.0} MiB/s", result.speed_mib_s);
+/// ```
+#[tauri::command]
+pub async fn benchmark_directory_write(
+    app: tauri::AppHandle,
+    dir: String,
+    size_mib: Option<u64>,
+) -> Result<WriteBenchmarkResult, String> {
+    let dir = PathBuf::from(dir);
+    let total_bytes = mib_to_bytes(size_mib.unwrap_or(DEFAULT_WRITE_SIZE_MIB))?;
+    if total_bytes == 0 {
+        return Err("Benchmark size must be greater than 0".to_string());
+    }
+    if !dir.is_dir() {
+        return Err(format!("Directory {} does not exist", dir.display()));
+    }
+    let available = disk::available_space(&dir)?;
+    if available < total_bytes {
+        return Err(format!(
+            "Not enough space for the benchmark: {} MiB required, {} MiB available",
+            total_bytes / MIB,
+            available / MIB
+        ));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = dir.join(format!(".sm-init-benchmark-{}.tmp", std::process::id()));
+        let result = write_benchmark(&app, &path, total_bytes);
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                eprintln!("Failed to remove benchmark file {:?}: {}", path, e);
+            }
+        }
+        result
+    })
+    .await
+    .map_err(|e| format!("Failed to run benchmark: {}", e))?
+}
+
+/// Writes `total_bytes` to `path` and measures the throughput
+fn write_benchmark(app: &tauri::AppHandle, path: &Path, total_bytes: u64) -> Result<WriteBenchmarkResult, String> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    let (mut file, direct_io) =
+        open_uncached(path, &options).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+
+    let mut buffer = AlignedBuffer::new(BLOCK_SIZE);
+    fill_random(buffer.as_mut_slice());
+
+    let started = Instant::now();
+    let mut last_progress = started;
+    let mut written = 0;
+    while written < total_bytes {
+        // Sizes are whole MiB, so every write stays a multiple of the alignment
+        let len = (total_bytes - written).min(BLOCK_SIZE as u64) as usize;
+        file.write_all(&buffer.as_slice()[..len])
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written += len as u64;
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            emit_progress(
+                app,
+                DiskBenchmarkProgress {
+                    kind: "write",
+                    bytes_done: written,
+                    total_bytes,
+                    speed_mib_s: speed_mib_s(written, started.elapsed()),
+                },
+            );
+        }
+    }
+    file.sync_data()
+        .map_err(|e| format!("Failed to flush {}: {}", path.display(), e))?;
+    let elapsed = started.elapsed();
+
+    emit_progress(
+        app,
+        DiskBenchmarkProgress {
+            kind: "write",
+            bytes_done: written,
+            total_bytes,
+            speed_mib_s: speed_mib_s(written, elapsed),
+        },
+    );
+    Ok(WriteBenchmarkResult {
+        bytes_written: written,
+        duration_s: elapsed.as_secs_f64(),
+        speed_mib_s: speed_mib_s(written, elapsed),
+        direct_io,
+    })
+}
+
+/// Measures the sustained read throughput of the PoS data in a directory
+///
+/// A sample spread across each `postdata_N.bin` file is read, bypassing
+/// the page cache, so that the result reflects the disk and not memory. The
+/// throughput is used to project how long a proving pass, which reads the whole
+/// data set, takes on this disk.
+///
+/// # Arguments
+///
+/// * `app` - Tauri application handle used to emit progress events
+/// * `dir` - PoS data directory
+/// * `sample_mib` - Amount of data to read in MiB, 2 GiB if omitted; all data if larger
+///
+/// # Returns
+///
+/// * `Ok(ReadBenchmarkResult)` - Read throughput and projected proving pass duration
+/// * `Err(String)` - If the sample size is too large, the directory holds no data files or they cannot be read
+///
+/// # Events
+///
+/// * 'disk-benchmark-progress' - Every 250 ms while reading
+///
+/// # Example
+///
+/// ```rust
+/// let result = benchmark_directory_read(app, "/path/to/post/data".to_string(), None).await?;
+/// println!("A proving pass reads the data in {:.0} s", result.projected_pass_s);
+/// ```
+#[tauri::command]
+pub async fn benchmark_directory_read(
+    app: tauri::AppHandle,
+    dir: String,
+    sample_mib: Option<u64>,
+) -> Result<ReadBenchmarkResult, String> {
+    let dir = PathBuf::from(dir);
+    let sample_bytes = mib_to_bytes(sample_mib.unwrap_or(DEFAULT_READ_SAMPLE_MIB))?;
+    if sample_bytes == 0 {
+        return Err("Sample size must be greater than 0".to_string());
+    }
+    let files = list_data_files(&dir).map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+    let files: Vec<(u32, u64)> = files.into_iter().filter(|(_, size)| *size > 0).collect();
+    if files.is_empty() {
+        return Err(format!("No PoS data files found in {}", dir.display()));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || read_benchmark(&app, &dir, &files, sample_bytes))
+        .await
+        .map_err(|e| format!("Failed to run benchmark: {}", e))?
+}
+
+/// Converts a size in MiB given by the frontend to bytes, failing if it does not fit
+fn mib_to_bytes(mib: u64) -> Result<u64, String> {
+    mib.checked_mul(MIB)
+        .ok_or_else(|| format!("Benchmark size of {} MiB is too large", mib))
+}
+
+/// Splits the share of a file into reads of at most one block spread evenly across the file
+///
+/// Sampling the whole file rather than its start keeps the measurement from
+/// depending on where the filesystem placed the beginning of each file.
+/// Offsets and lengths are multiples of [`ALIGNMENT`]; the last read may reach
+/// beyond the end of the file.
+///
+/// # Returns
+///
+/// * `Vec<(u64, u64)>` - Offset and length of every read, in file order
+fn sample_ranges(file_size: u64, share: u64) -> Vec<(u64, u64)> {
+    let block = BLOCK_SIZE as u64;
+    let share = share.min(file_size.next_multiple_of(ALIGNMENT as u64));
+    let reads = share.div_ceil(block);
+    if reads == 0 {
+        return Vec::new();
+    }
+    let stride = (file_size / reads / ALIGNMENT as u64 * ALIGNMENT as u64).max(block);
+    let mut remaining = share;
+    (0..reads)
+        .map(|read| {
+            let len = remaining.min(block);
+            remaining -= len;
+            (read * stride, len)
+        })
+        .collect()
+}
+
+/// Reads an equal share of `sample_bytes` spread across each file and measures the throughput
+fn read_benchmark(
+    app: &tauri::AppHandle,
+    dir: &Path,
+    files: &[(u32, u64)],
+    sample_bytes: u64,
+) -> Result<ReadBenchmarkResult, String> {
+    let files_on_disk: u64 = files.iter().map(|(_, size)| size).sum();
+    let data_size = PostMetadata::read(dir)
+        .map(|metadata| metadata.layout(dir).total_bytes())
+        .unwrap_or(files_on_disk);
+
+    // Equal share per file, rounded up to whole blocks of the alignment
+    let share = sample_bytes.div_ceil(files.len() as u64).next_multiple_of(ALIGNMENT as u64);
+    let samples: Vec<Vec<(u64, u64)>> = files.iter().map(|(_, size)| sample_ranges(*size, share)).collect();
+    let total_bytes: u64 = files
+        .iter()
+        .zip(&samples)
+        .flat_map(|((_, size), ranges)| ranges.iter().map(move |(offset, len)| (*len).min(size - offset)))
+        .sum();
+
+    let mut options = OpenOptions::new();
+    options.read(true);
+    let mut buffer = AlignedBuffer::new(BLOCK_SIZE);
+    let mut direct_io = true;
+
+    let started = Instant::now();
+    let mut last_progress = started;
+    let mut read = 0;
+    for ((index, _), ranges) in files.iter().zip(&samples) {
+        let path = dir.join(format!("postdata_{}.bin", index));
+        let (mut file, uncached) =
+            open_uncached(&path, &options).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        direct_io &= uncached || evict_cached(&file);
+
+        for (offset, len) in ranges {
+            file.seek(SeekFrom::Start(*offset))
+                .map_err(|e| format!("Failed to seek {}: {}", path.display(), e))?;
+            let mut range_read = 0;
+            while range_read < *len {
+                // Lengths stay multiples of the alignment, the last read may return less at the end of the file
+                let n = file
+                    .read(&mut buffer.as_mut_slice()[..(len - range_read) as usize])
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                if n == 0 {
+                    break;
+                }
+                range_read += n as u64;
+                read += n as u64;
+
+                if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                    last_progress = Instant::now();
+                    emit_progress(
+                        app,
+                        DiskBenchmarkProgress {
+                            kind: "read",
+                            bytes_done: read,
+                            total_bytes,
+                            speed_mib_s: speed_mib_s(read, started.elapsed()),
+                        },
+                    );
+                }
+            }
+        }
+    }
+    let elapsed = started.elapsed();
+    let speed = speed_mib_s(read, elapsed);
+
+    emit_progress(
+        app,
+        DiskBenchmarkProgress {
+            kind: "read",
+            bytes_done: read,
+            total_bytes,
+            speed_mib_s: speed,
+        },
+    );
+    Ok(ReadBenchmarkResult {
+        bytes_read: read,
+        files_sampled: files.len() as u32,
+        duration_s: elapsed.as_secs_f64(),
+        speed_mib_s: speed,
+        direct_io,
+        data_size,
+        projected_pass_s: if speed > 0.0 { data_size as f64 / MIB as f64 / speed } else { 0.0 },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: u64 = BLOCK_SIZE as u64;
+
+    #[test]
+    fn rejects_sizes_overflowing_bytes() {
+        assert_eq!(mib_to_bytes(2), Ok(2 * MIB));
+        assert_eq!(mib_to_bytes(u64::MAX / MIB), Ok(u64::MAX / MIB * MIB));
+        assert!(mib_to_bytes(u64::MAX / MIB + 1).is_err());
+        assert!(mib_to_bytes(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn spreads_samples_across_file() {
+        // Two blocks out of a 100 block file are read from its start and its middle
+        assert_eq!(sample_ranges(100 * BLOCK, 2 * BLOCK), vec![(0, BLOCK), (50 * BLOCK, BLOCK)]);
+        // The last read is shorter if the share is not a whole number of blocks
+        assert_eq!(
+            sample_ranges(100 * BLOCK, BLOCK + 4096),
+            vec![(0, BLOCK), (50 * BLOCK, 4096)]
+        );
+    }
+
+    #[test]
+    fn reads_small_files_sequentially() {
+        assert_eq!(sample_ranges(2 * BLOCK, 8 * BLOCK), vec![(0, BLOCK), (BLOCK, BLOCK)]);
+        // Lengths stay aligned beyond the end of the file
+        assert_eq!(sample_ranges(1000, BLOCK), vec![(0, 4096)]);
+        assert!(sample_ranges(0, BLOCK).is_empty());
+    }
+}
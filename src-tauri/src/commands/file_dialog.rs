@@ -4,16 +4,30 @@
 //! It includes commands for selecting directories, verifying their validity, and checking specific
 //! directory properties like write permissions and available space.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use tauri::api::dialog::FileDialogBuilder;
-use sys_info;
+
+use super::disk::{self, FilesystemInfo};
+use super::pos_data::{PosLayout, DEFAULT_MAX_FILE_SIZE};
+use super::pos_metadata::PostMetadata;
+
+/// Free space required when no initialization size is known yet (1 GiB)
+const MIN_REQUIRED_SPACE: u64 = 1024 * 1024 * 1024;
 
 #[derive(Debug, serde::Serialize)]
 pub struct DirectoryValidation {
     exists: bool,
     has_write_permission: bool,
     has_space: bool,
+    /// Free space on the filesystem holding the directory in bytes
+    available_space: Option<u64>,
+    /// Space the planned initialization still needs in bytes
+    required_space: u64,
+    /// Filesystem holding the directory, `None` if it cannot be determined
+    filesystem: Option<FilesystemInfo>,
+    /// Problems that do not prevent using the directory, e.g. an unsuited filesystem
+    warnings: Vec<String>,
     error: Option<String>,
 }
 
@@ -56,26 +70,43 @@ pub async fn select_directory() -> Result<String, String> {
     Ok(selected_dir)
 }
 
-/// Retrieves the available disk space for a given path
+/// Computes the free space needed to complete an initialization in a directory
 /// 
-/// This helper function uses the sys_info crate to get disk information
-/// and returns the available space in bytes.
+/// Files already present in the directory are subtracted, so resuming an
+/// initialization only requires the space of the data still to be written.
+/// If the directory holds PoS metadata, its labels per unit and maximum file
+/// size are used, as PostCLI resumes with them.
 /// 
 /// # Arguments
 /// 
-/// * `_path` - PathBuf representing the directory to check
+/// * `path` - Directory the data is written to
+/// * `num_units` - Planned number of space units, `None` if not chosen yet
+/// * `max_file_size` - Planned maximum size of a data file, PostCLI's default if omitted
 /// 
 /// # Returns
 /// 
-/// * `Ok(u64)` - Available space in bytes
-/// * `Err(String)` - Error message if disk info cannot be retrieved
-fn get_available_space(_path: &PathBuf) -> Result<u64, String> {
-    let disk_info = sys_info::disk_info()
-        .map_err(|e| format!("Failed to get disk info: {}", e))?;
-    
-    // Convert KB to bytes (sys_info returns values in KB)
-    let free_space = disk_info.free * 1024;
-    Ok(free_space)
+/// * `u64` - Required space in bytes, 1 GiB if the number of units is unknown
+fn get_required_space(path: &Path, num_units: Option<u32>, max_file_size: Option<u64>) -> u64 {
+    let num_units = match num_units {
+        Some(num_units) => num_units,
+        None => return MIN_REQUIRED_SPACE,
+    };
+    let mut layout = PosLayout::from_args(&[
+        format!("-numUnits={}", num_units),
+        format!("-datadir={}", path.display()),
+    ]);
+    if let Ok(metadata) = PostMetadata::read(path) {
+        layout.labels_per_unit = metadata.labels_per_unit;
+        layout.max_file_size = metadata.max_file_size;
+    } else if let Some(max_file_size) = max_file_size {
+        layout.max_file_size = max_file_size;
+    }
+    layout.remaining_bytes()
+}
+
+/// Formats a size in bytes as GiB for error messages
+fn format_gib(bytes: u64) -> String {
+    format!("{:.2} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
 }
 
 /// Performs comprehensive validation of a directory
@@ -83,11 +114,17 @@ fn get_available_space(_path: &PathBuf) -> Result<u64, String> {
 /// This command checks multiple aspects of a directory:
 /// - Existence
 /// - Write permissions
-/// - Available space (minimum 1GB required)
+/// - Available space on the filesystem holding the directory, compared with the
+///   space the planned initialization still needs (1 GiB if not planned yet)
+/// 
+/// It also detects the filesystem holding the directory and returns warnings if
+/// it is unsuited for PoS data, e.g. FAT32, network shares or removable drives.
 /// 
 /// # Arguments
 /// 
 /// * `path` - String representing the directory path to validate
+/// * `num_units` - Planned number of space units
+/// * `max_file_size` - Planned maximum size of a data file, PostCLI's default if omitted
 /// 
 /// # Returns
 /// 
@@ -97,18 +134,26 @@ fn get_available_space(_path: &PathBuf) -> Result<u64, String> {
 /// # Example
 /// 
 /// ```rust
-/// let validation = verify_directory("/path/to/directory".to_string()).await?;
+/// let validation = verify_directory("/path/to/directory".to_string(), Some(4), None).await?;
 /// if validation.exists && validation.has_write_permission && validation.has_space {
 ///     println!("Directory is valid for use");
 /// }
 /// ```
 #[tauri::command]
-pub async fn verify_directory(path: String) -> Result<DirectoryValidation, String> {
+pub async fn verify_directory(
+    path: String,
+    num_units: Option<u32>,
+    max_file_size: Option<u64>,
+) -> Result<DirectoryValidation, String> {
     let path = PathBuf::from(path);
     let mut validation = DirectoryValidation {
         exists: false,
         has_write_permission: false,
         has_space: false,
+        available_space: None,
+        required_space: 0,
+        filesystem: None,
+        warnings: Vec::new(),
         error: None,
     };
 
@@ -119,6 +164,15 @@ pub async fn verify_directory(path: String) -> Result<DirectoryValidation, Strin
         return Ok(validation);
     }
 
+    // Detect the filesystem, problems with it are reported as warnings only
+    match disk::filesystem_info(&path) {
+        Ok(info) => {
+            validation.warnings = disk::filesystem_warnings(&info, max_file_size.unwrap_or(DEFAULT_MAX_FILE_SIZE));
+            validation.filesystem = Some(info);
+        }
+        Err(e) => eprintln!("Failed to detect filesystem of {:?}: {}", path, e),
+    }
+
     // Check write permissions by attempting to create a temporary file
     let temp_file_path = path.join(".write_test_temp");
     match fs::write(&temp_file_path, b"test") {
@@ -135,15 +189,16 @@ pub async fn verify_directory(path: String) -> Result<DirectoryValidation, Strin
     }
 
     // Check available space
-    match get_available_space(&path) {
+    validation.required_space = get_required_space(&path, num_units, max_file_size);
+    match disk::available_space(&path) {
         Ok(space) => {
-            // Require at least 1GB of free space
-            const MIN_REQUIRED_SPACE: u64 = 1024 * 1024 * 1024; // 1GB in bytes
-            validation.has_space = space >= MIN_REQUIRED_SPACE;
+            validation.available_space = Some(space);
+            validation.has_space = space >= validation.required_space;
             if !validation.has_space {
                 validation.error = Some(format!(
-                    "Insufficient disk space. Required: 1GB, Available: {:.2} GB",
-                    space as f64 / (1024.0 * 1024.0 * 1024.0)
+                    "Insufficient disk space. Required: {}, Available: {}",
+                    format_gib(validation.required_space),
+                    format_gib(space)
                 ));
             }
         }
@@ -158,11 +213,14 @@ pub async fn verify_directory(path: String) -> Result<DirectoryValidation, Strin
 
 /// Checks if a directory has sufficient free space
 /// 
-/// This command verifies if the specified directory has at least 1GB of free space.
+/// This command verifies if the filesystem holding the specified directory has enough
+/// free space for the rest of the planned initialization, or 1 GiB if none is planned.
 /// 
 /// # Arguments
 /// 
 /// * `path` - String representing the directory path to check
+/// * `num_units` - Planned number of space units
+/// * `max_file_size` - Planned maximum size of a data file, PostCLI's default if omitted
 /// 
 /// # Returns
 /// 
@@ -172,24 +230,24 @@ pub async fn verify_directory(path: String) -> Result<DirectoryValidation, Strin
 /// # Example
 /// 
 /// ```rust
-/// if check_directory_space("/path/to/directory".to_string()).await? {
+/// if check_directory_space("/path/to/directory".to_string(), Some(4), None).await? {
 ///     println!("Directory has sufficient space");
 /// }
 /// ```
 #[tauri::command]
-pub async fn check_directory_space(path: String) -> Result<bool, String> {
+pub async fn check_directory_space(
+    path: String,
+    num_units: Option<u32>,
+    max_file_size: Option<u64>,
+) -> Result<bool, String> {
     let path = PathBuf::from(path);
     
     if !path.exists() || !path.is_dir() {
         return Err("Directory does not exist".to_string());
     }
 
-    match get_available_space(&path) {
-        Ok(space) => {
-            // Require at least 1GB of free space
-            const MIN_REQUIRED_SPACE: u64 = 1024 * 1024 * 1024; // 1GB in bytes
-            Ok(space >= MIN_REQUIRED_SPACE)
-        }
+    match disk::available_space(&path) {
+        Ok(space) => Ok(space >= get_required_space(&path, num_units, max_file_size)),
         Err(e) => Err(format!("Failed to check disk space: {}", e)),
     }
 }
@@ -234,3 +292,42 @@ pub async fn check_write_permission(path: String) -> Result<bool, String> {
         Err(_) => Ok(false),
     }
 }
+
+/// Detects the filesystem holding a directory
+/// 
+/// # Arguments
+/// 
+/// * `path` - String representing the directory path to check
+/// 
+/// # Returns
+/// 
+/// * `Ok(FilesystemInfo)` - Filesystem type, mount point, device and storage kind
+/// * `Err(String)` - Error message if the filesystem cannot be determined
+/// 
+/// # Example
+/// 
+/// ```rust
+/// let info = get_filesystem_info("/path/to/directory".to_string()).await?;
+/// println!("Directory is on {} mounted at {:?}", info.fs_type, info.mount_point);
+/// ```
+#[tauri::command]
+pub async fn get_filesystem_info(path: String) -> Result<FilesystemInfo, String> {
+    disk::filesystem_info(&PathBuf::from(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::data_dir;
+
+    #[test]
+    fn sizes_new_directory_with_planned_file_size() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        // One MainNet unit is 64 GiB; postdata_5.bin only exists with 4 GiB files
+        let dir = data_dir("required-space", &[0, 0, 0, 0, 0, 1024]);
+        assert_eq!(get_required_space(&dir, None, None), MIN_REQUIRED_SPACE);
+        assert_eq!(get_required_space(&dir, Some(1), None), 64 * GIB - 1024);
+        assert_eq!(get_required_space(&dir, Some(1), Some(32 * GIB)), 64 * GIB);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
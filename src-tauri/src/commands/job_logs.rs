@@ -0,0 +1,455 @@
+//! PostCLI Job Logs Module
+//!
+//! This module writes the stdout/stderr of every detached PostCLI job to its own
+//! log file in the application log directory, so that the output of a job can be
+//! reviewed even if the UI was not listening while it ran.
+//!
+//! Log files are named after the job's start time and PID
+//! (`postcli-20241217-223457-1234.log`). A file is rotated once it exceeds
+//! [`MAX_LOG_FILE_SIZE`], keeping up to [`MAX_ROTATED_FILES`] older parts
+//! (`.1.log` being the most recent). On startup, logs older than [`RETENTION_DAYS`]
+//! are deleted and at most [`MAX_JOB_LOGS`] job logs are kept.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+use tauri::State;
+
+use super::jobs::Job;
+use super::log_parser;
+
+/// Size after which a log file is rotated
+pub const MAX_LOG_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Number of rotated parts kept per job log
+pub const MAX_ROTATED_FILES: u32 = 3;
+
+/// Age after which job logs are deleted
+pub const RETENTION_DAYS: u64 = 30;
+
+/// Maximum number of job logs kept
+pub const MAX_JOB_LOGS: usize = 50;
+
+/// Default number of matches returned by `search_job_log`
+const DEFAULT_SEARCH_LIMIT: usize = 500;
+
+/// Log levels in increasing order of severity
+const SEVERITIES: [&str; 6] = ["DEBUG", "INFO", "WARN", "ERROR", "PANIC", "FATAL"];
+
+/// Summary of a job log returned by `list_job_logs`
+#[derive(Debug, Serialize)]
+pub struct JobLogInfo {
+    /// Name of the log, used to retrieve it
+    pub name: String,
+    /// Total size of the log including rotated parts in bytes
+    pub size: u64,
+    /// Time of the last write as seconds since the Unix epoch
+    pub modified_at: u64,
+}
+
+/// Line of a job log matching a search
+#[derive(Debug, Serialize)]
+pub struct LogMatch {
+    /// Line number counted from the start of the oldest part, starting at 1
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// Tauri-managed location of the job logs
+pub struct JobLogs {
+    dir: Option<PathBuf>,
+}
+
+impl JobLogs {
+    /// Resolves the job log directory and applies the retention policy
+    ///
+    /// If the log directory cannot be determined, job output is not logged to files.
+    pub fn new(app: &tauri::AppHandle) -> JobLogs {
+        let dir = app.path_resolver().app_log_dir().map(|dir| dir.join("postcli"));
+        if let Some(dir) = &dir {
+            if let Err(e) = fs::create_dir_all(dir) {
+                eprintln!("Failed to create job log directory {:?}: {}", dir, e);
+            }
+        }
+        let logs = JobLogs { dir };
+        logs.apply_retention();
+        logs
+    }
+
+    /// Creates the log file of a job that was just started
+    pub fn create(&self, job: &Job) -> Option<Arc<JobLog>> {
+        let dir = self.dir.as_ref()?;
+        let started_at: DateTime<Local> = (UNIX_EPOCH + Duration::from_secs(job.started_at_secs())).into();
+        let name = format!("postcli-{}-{}", started_at.format("%Y%m%d-%H%M%S"), job.pid);
+        match JobLog::open(dir.clone(), name) {
+            Ok(log) => {
+                log.write_line("app", &format!("postcli job {} started with args: {:?}", job.id, job.args));
+                Some(Arc::new(log))
+            }
+            Err(e) => {
+                eprintln!("Failed to create log file for postcli job {}: {}", job.id, e);
+                None
+            }
+        }
+    }
+
+    /// Returns the parts of a job log, oldest first
+    fn parts(&self, name: &str) -> Result<Vec<PathBuf>, String> {
+        if name.is_empty() || name.contains(['/', '\\', '.']) {
+            return Err(format!("Invalid job log name {}", name));
+        }
+        let dir = self.dir.as_ref().ok_or("Job logs are not available")?;
+        let mut parts: Vec<PathBuf> = (1..=MAX_ROTATED_FILES)
+            .rev()
+            .map(|index| dir.join(format!("{}.{}.log", name, index)))
+            .filter(|path| path.exists())
+            .collect();
+        let current = dir.join(format!("{}.log", name));
+        if current.exists() {
+            parts.push(current);
+        }
+        if parts.is_empty() {
+            return Err(format!("Unknown job log {}", name));
+        }
+        Ok(parts)
+    }
+
+    /// Groups the files of the log directory by job log name
+    fn logs(&self) -> HashMap<String, Vec<(PathBuf, fs::Metadata)>> {
+        let mut logs: HashMap<String, Vec<(PathBuf, fs::Metadata)>> = HashMap::new();
+        let entries = match self.dir.as_ref().and_then(|dir| fs::read_dir(dir).ok()) {
+            Some(entries) => entries,
+            None => return logs,
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let name = match file_name.strip_suffix(".log") {
+                Some(stem) => stem.split('.').next().unwrap_or(stem).to_string(),
+                None => continue,
+            };
+            if let Ok(metadata) = entry.metadata() {
+                logs.entry(name).or_default().push((entry.path(), metadata));
+            }
+        }
+        logs
+    }
+
+    /// Deletes expired job logs and the oldest ones beyond the maximum count
+    fn apply_retention(&self) {
+        let cutoff = SystemTime::now() - Duration::from_secs(RETENTION_DAYS * 24 * 60 * 60);
+        let mut logs: Vec<(SystemTime, Vec<PathBuf>)> = self
+            .logs()
+            .into_values()
+            .map(|files| {
+                let modified = files
+                    .iter()
+                    .filter_map(|(_, metadata)| metadata.modified().ok())
+                    .max()
+                    .unwrap_or(UNIX_EPOCH);
+                (modified, files.into_iter().map(|(path, _)| path).collect())
+            })
+            .collect();
+        logs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+        for (index, (modified, files)) in logs.iter().enumerate() {
+            if index >= MAX_JOB_LOGS || *modified < cutoff {
+                for path in files {
+                    if let Err(e) = fs::remove_file(path) {
+                        eprintln!("Failed to delete old job log {:?}: {}", path, e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the last `lines` lines of a job log, oldest first
+    fn tail(&self, name: &str, lines: usize) -> Result<Vec<String>, String> {
+        let mut tail = VecDeque::with_capacity(lines);
+        for line in read_lines(self, name)? {
+            if tail.len() == lines {
+                tail.pop_front();
+            }
+            if lines > 0 {
+                tail.push_back(line);
+            }
+        }
+        Ok(tail.into())
+    }
+
+    /// Returns up to `limit` lines of a job log containing `query` and logged at `min_severity` or above
+    fn search(
+        &self,
+        name: &str,
+        query: Option<String>,
+        min_severity: Option<String>,
+        limit: usize,
+    ) -> Result<Vec<LogMatch>, String> {
+        let query = query.map(|q| q.to_lowercase()).filter(|q| !q.is_empty());
+        let min_rank = match &min_severity {
+            Some(level) => Some(severity_rank(level).ok_or_else(|| format!("Unknown severity {}", level))?),
+            None => None,
+        };
+
+        let mut matches = Vec::new();
+        for (index, line) in read_lines(self, name)?.enumerate() {
+            if matches.len() >= limit {
+                break;
+            }
+            if let Some(query) = &query {
+                if !line.to_lowercase().contains(query) {
+                    continue;
+                }
+            }
+            if let Some(min_rank) = min_rank {
+                // Skip the timestamp and stream prefix added when the line was logged
+                let output = line.split_once(": ").map_or(line.as_str(), |(_, output)| output);
+                let rank = log_parser::line_level(output).and_then(severity_rank);
+                if rank.is_none_or(|rank| rank < min_rank) {
+                    continue;
+                }
+            }
+            matches.push(LogMatch {
+                line_number: index + 1,
+                line,
+            });
+        }
+        Ok(matches)
+    }
+}
+
+/// Log file of a single job, shared by its output reader threads
+pub struct JobLog {
+    dir: PathBuf,
+    name: String,
+    file: Mutex<(File, u64)>,
+}
+
+impl JobLog {
+    /// Opens (or creates) the current part of a job log for appending
+    fn open(dir: PathBuf, name: String) -> std::io::Result<JobLog> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(format!("{}.log", name)))?;
+        let size = file.metadata()?.len();
+        Ok(JobLog {
+            dir,
+            name,
+            file: Mutex::new((file, size)),
+        })
+    }
+
+    /// Appends a timestamped line, rotating the file once it is too large
+    pub fn write_line(&self, stream: &str, line: &str) {
+        let entry = format!("{} {}: {}\n", Utc::now().to_rfc3339(), stream, line);
+        let mut file = self.file.lock().unwrap();
+        if file.1 + entry.len() as u64 > MAX_LOG_FILE_SIZE && file.1 > 0 {
+            match self.rotate() {
+                Ok(new_file) => *file = (new_file, 0),
+                Err(e) => eprintln!("Failed to rotate job log {}: {}", self.name, e),
+            }
+        }
+        match file.0.write_all(entry.as_bytes()) {
+            Ok(()) => file.1 += entry.len() as u64,
+            Err(e) => eprintln!("Failed to write job log {}: {}", self.name, e),
+        }
+    }
+
+    /// Shifts the rotated parts by one and starts a new current part
+    fn rotate(&self) -> std::io::Result<File> {
+        let part = |index: u32| self.dir.join(format!("{}.{}.log", self.name, index));
+        let oldest = part(MAX_ROTATED_FILES);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for index in (1..MAX_ROTATED_FILES).rev() {
+            if part(index).exists() {
+                fs::rename(part(index), part(index + 1))?;
+            }
+        }
+        let current = self.dir.join(format!("{}.log", self.name));
+        fs::rename(&current, part(1))?;
+        File::create(current)
+    }
+}
+
+/// Reads all lines of a job log, oldest part first
+fn read_lines(logs: &JobLogs, name: &str) -> Result<impl Iterator<Item = String>, String> {
+    let mut readers = Vec::new();
+    for path in logs.parts(name)? {
+        let file = File::open(&path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+        readers.push(BufReader::new(file));
+    }
+    Ok(readers
+        .into_iter()
+        .flat_map(|reader| reader.lines().map_while(Result::ok)))
+}
+
+/// Returns the rank of a log level in [`SEVERITIES`]
+fn severity_rank(level: &str) -> Option<usize> {
+    let level = level.to_ascii_uppercase();
+    let level = if level == "WARNING" { "WARN".to_string() } else { level };
+    SEVERITIES.iter().position(|s| *s == level)
+}
+
+/// Lists the job logs, most recently written first
+///
+/// # Returns
+///
+/// * `Vec<JobLogInfo>` - Name, total size and last modification time of every job log
+#[tauri::command]
+pub fn list_job_logs(logs: State<'_, JobLogs>) -> Vec<JobLogInfo> {
+    let mut list: Vec<JobLogInfo> = logs
+        .logs()
+        .into_iter()
+        .map(|(name, files)| JobLogInfo {
+            name,
+            size: files.iter().map(|(_, metadata)| metadata.len()).sum(),
+            modified_at: files
+                .iter()
+                .filter_map(|(_, metadata)| metadata.modified().ok())
+                .max()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        })
+        .collect();
+    list.sort_by(|a, b| b.modified_at.cmp(&a.modified_at).then_with(|| b.name.cmp(&a.name)));
+    list
+}
+
+/// Returns the last lines of a job log
+///
+/// # Arguments
+///
+/// * `name` - Name of the job log
+/// * `lines` - Number of lines to return
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - Up to `lines` lines, oldest first
+/// * `Err(String)` - If the log does not exist or cannot be read
+#[tauri::command]
+pub fn tail_job_log(name: String, lines: usize, logs: State<'_, JobLogs>) -> Result<Vec<String>, String> {
+    logs.tail(&name, lines)
+}
+
+/// Searches a job log by substring and/or severity
+///
+/// # Arguments
+///
+/// * `name` - Name of the job log
+/// * `query` - Case-insensitive substring the line must contain
+/// * `min_severity` - Lowest PostCLI log level to match (DEBUG, INFO, WARN, ERROR, PANIC, FATAL);
+///   lines without a level never match a severity filter
+/// * `limit` - Maximum number of matches (default 500)
+///
+/// # Returns
+///
+/// * `Ok(Vec<LogMatch>)` - Matching lines with their line numbers, oldest first
+/// * `Err(String)` - If the log does not exist, cannot be read or the severity is unknown
+#[tauri::command]
+pub fn search_job_log(
+    name: String,
+    query: Option<String>,
+    min_severity: Option<String>,
+    limit: Option<usize>,
+    logs: State<'_, JobLogs>,
+) -> Result<Vec<LogMatch>, String> {
+    logs.search(&name, query, min_severity, limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::temp_dir;
+
+    const NAME: &str = "postcli-20241217-223457-1234";
+
+    fn write_parts(dir: &std::path::Path, lines: &[&str]) {
+        let log = JobLog::open(dir.to_path_buf(), NAME.to_string()).unwrap();
+        for line in lines {
+            log.write_line("stdout", line);
+            // Pretend the part is full, so the next line starts a new one
+            log.file.lock().unwrap().1 = MAX_LOG_FILE_SIZE;
+        }
+    }
+
+    fn set_modified(path: &std::path::Path, age: Duration) {
+        let file = OpenOptions::new().append(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn rotates_and_tails_across_parts() {
+        let dir = temp_dir("job-logs-rotate");
+        write_parts(&dir, &["line 0", "line 1", "line 2", "line 3", "line 4"]);
+        let logs = JobLogs { dir: Some(dir.clone()) };
+
+        // The oldest line was rotated out after more than MAX_ROTATED_FILES rotations
+        assert_eq!(logs.parts(NAME).unwrap().len(), MAX_ROTATED_FILES as usize + 1);
+        assert!(!dir.join(format!("{}.{}.log", NAME, MAX_ROTATED_FILES + 1)).exists());
+        let all = logs.tail(NAME, 10).unwrap();
+        assert_eq!(all.len(), 4);
+        assert!(all[0].ends_with("stdout: line 1"));
+        let tail = logs.tail(NAME, 2).unwrap();
+        assert!(tail[0].ends_with("stdout: line 3"));
+        assert!(tail[1].ends_with("stdout: line 4"));
+        assert!(logs.tail(NAME, 0).unwrap().is_empty());
+
+        assert!(logs.tail("unknown", 1).is_err());
+        assert!(logs.tail("../postcli", 1).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn searches_by_query_and_severity() {
+        let dir = temp_dir("job-logs-search");
+        let log = JobLog::open(dir.clone(), NAME.to_string()).unwrap();
+        log.write_line("stderr", "2024-12-17T22:34:57.123Z\tINFO\tinitialization started");
+        log.write_line("stderr", "2024-12-17T22:34:58.123Z\tWARN\tdisk almost full");
+        log.write_line("stderr", "2024-12-17T22:34:59.123Z\tERROR\tinitialization failed");
+        log.write_line("stdout", "no level");
+        let logs = JobLogs { dir: Some(dir.clone()) };
+
+        let lines = |matches: Vec<LogMatch>| matches.iter().map(|m| m.line_number).collect::<Vec<_>>();
+        let search = |query: Option<&str>, severity: Option<&str>, limit| {
+            logs.search(NAME, query.map(String::from), severity.map(String::from), limit)
+        };
+        assert_eq!(lines(search(Some("INITIALIZATION"), None, 10).unwrap()), vec![1, 3]);
+        assert_eq!(lines(search(None, Some("warning"), 10).unwrap()), vec![2, 3]);
+        assert_eq!(lines(search(Some("initialization"), Some("ERROR"), 10).unwrap()), vec![3]);
+        assert_eq!(lines(search(None, None, 2).unwrap()), vec![1, 2]);
+        assert!(search(None, Some("LOUD"), 10).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn deletes_expired_and_excess_logs() {
+        let dir = temp_dir("job-logs-retention");
+        let expired = Duration::from_secs((RETENTION_DAYS + 1) * 24 * 60 * 60);
+        for name in ["expired.log", "expired.1.log"] {
+            fs::write(dir.join(name), "old").unwrap();
+            set_modified(&dir.join(name), expired);
+        }
+        for index in 0..MAX_JOB_LOGS + 1 {
+            let path = dir.join(format!("recent-{}.log", index));
+            fs::write(&path, "new").unwrap();
+            set_modified(&path, Duration::from_secs(60 * (index as u64 + 1)));
+        }
+        let logs = JobLogs { dir: Some(dir.clone()) };
+        logs.apply_retention();
+
+        let remaining = logs.logs();
+        assert_eq!(remaining.len(), MAX_JOB_LOGS);
+        assert!(!remaining.contains_key("expired"));
+        assert!(remaining.contains_key("recent-0"));
+        assert!(!remaining.contains_key(&format!("recent-{}", MAX_JOB_LOGS)));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,313 @@
+//! PostCLI Job Store Module
+//!
+//! This module persists a record of every running PostCLI job (arguments, data
+//! directory, PID, start time and last measured progress) in the application data
+//! directory, so that an initialization survives the app window being closed or
+//! the app crashing.
+//!
+//! On startup, [`recover_jobs`] checks every recorded job: if its PostCLI process
+//! is still alive, the application re-attaches to it and, once the rest of the
+//! application state is in place, [`watch_recovered_jobs`] monitors its data
+//! directory and waits for its exit; otherwise the record is kept as interrupted so the user can resume it with the
+//! same arguments (PostCLI continues from the files already present).
+//!
+//! PostCLI writes its stdout and stderr to files next to the records instead of
+//! pipes to the application, so it keeps running when the application exits.
+//! After a restart the output of a re-attached job is followed from these files.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use super::job_logs::JobLogs;
+use super::jobs::{self, Job, JobRegistry};
+use super::monitor::{self, DiskProgress};
+use super::pos_data::PosLayout;
+use super::postcli;
+use super::process;
+
+/// Name of the file holding the job records in the app data directory
+const STORE_FILE: &str = "postcli-jobs.json";
+
+/// Name of the directory holding the output files of running jobs in the app data directory
+const OUTPUT_DIR: &str = "postcli-output";
+
+/// Files PostCLI writes its stdout and stderr to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputFiles {
+    pub stdout: PathBuf,
+    pub stderr: PathBuf,
+}
+
+impl OutputFiles {
+    /// Deletes the output files
+    pub fn remove(&self) {
+        for path in [&self.stdout, &self.stderr] {
+            if let Err(e) = fs::remove_file(path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    eprintln!("Failed to delete postcli output {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+}
+
+/// Last progress measured for a recorded job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedProgress {
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+    pub percentage: f64,
+    /// Time of the measurement as seconds since the Unix epoch
+    pub updated_at: u64,
+}
+
+/// Persisted record of a PostCLI job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    /// Stable identifier of the record, derived from the start time and PID
+    pub id: String,
+    pub args: Vec<String>,
+    pub datadir: String,
+    pub pid: u32,
+    /// Start time as seconds since the Unix epoch
+    pub started_at: u64,
+    pub last_progress: Option<RecordedProgress>,
+    /// Whether the job's process was found dead on startup
+    #[serde(default)]
+    pub interrupted: bool,
+    /// Files holding the job's output, `None` for records of older versions
+    #[serde(default)]
+    pub output: Option<OutputFiles>,
+    /// Whether the job's process is suspended, restored when re-attaching to it
+    #[serde(default)]
+    pub paused: bool,
+}
+
+impl JobRecord {
+    /// Creates the record of a job that was just started
+    pub fn new(job: &Job, datadir: &str, output: OutputFiles) -> JobRecord {
+        JobRecord {
+            id: record_id(job),
+            args: job.args.clone(),
+            datadir: datadir.to_string(),
+            pid: job.pid,
+            started_at: job.started_at_secs(),
+            last_progress: None,
+            interrupted: false,
+            output: Some(output),
+            paused: false,
+        }
+    }
+}
+
+/// Returns the id of the record belonging to a job
+pub fn record_id(job: &Job) -> String {
+    format!("{}-{}", job.started_at_secs(), job.pid)
+}
+
+/// Tauri-managed store of job records backed by a JSON file
+pub struct JobStore {
+    path: Option<PathBuf>,
+    records: Mutex<Vec<JobRecord>>,
+}
+
+impl JobStore {
+    /// Loads the job records from the application data directory
+    ///
+    /// A missing or unreadable file results in an empty store. If the data
+    /// directory cannot be determined, records are only kept in memory.
+    pub fn load(app: &tauri::AppHandle) -> JobStore {
+        let path = app.path_resolver().app_data_dir().map(|dir| dir.join(STORE_FILE));
+        let records = path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        JobStore {
+            path,
+            records: Mutex::new(records),
+        }
+    }
+
+    /// Adds a record and persists the store
+    pub fn insert(&self, record: JobRecord) {
+        let mut records = self.records.lock().unwrap();
+        records.retain(|r| r.id != record.id);
+        records.push(record);
+        self.persist(&records);
+    }
+
+    /// Creates empty output files for a job about to be started
+    ///
+    /// The files are placed in the app data directory, or in the temporary
+    /// directory if it cannot be determined.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((OutputFiles, File, File))` - Paths of the files and the files opened for writing stdout and stderr
+    /// * `Err(String)` - Error message if a file could not be created
+    pub fn create_output_files(&self) -> Result<(OutputFiles, File, File), String> {
+        let dir = match self.path.as_ref().and_then(|p| p.parent()) {
+            Some(dir) => dir.join(OUTPUT_DIR),
+            None => std::env::temp_dir().join(OUTPUT_DIR),
+        };
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create postcli output directory {:?}: {}", dir, e))?;
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let output = OutputFiles {
+            stdout: dir.join(format!("{}.stdout", stamp)),
+            stderr: dir.join(format!("{}.stderr", stamp)),
+        };
+        let stdout = create_output_file(&output.stdout)?;
+        let stderr = create_output_file(&output.stderr)?;
+        Ok((output, stdout, stderr))
+    }
+
+    /// Removes a record with its output files and persists the store
+    pub fn remove(&self, id: &str) {
+        let mut records = self.records.lock().unwrap();
+        let index = match records.iter().position(|r| r.id == id) {
+            Some(index) => index,
+            None => return,
+        };
+        let record = records.remove(index);
+        self.persist(&records);
+        if let Some(output) = record.output {
+            output.remove();
+        }
+    }
+
+    /// Returns a copy of a record
+    pub fn get(&self, id: &str) -> Option<JobRecord> {
+        self.records.lock().unwrap().iter().find(|r| r.id == id).cloned()
+    }
+
+    /// Returns copies of all records matching a predicate
+    pub fn filter<F: Fn(&JobRecord) -> bool>(&self, predicate: F) -> Vec<JobRecord> {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| predicate(r))
+            .cloned()
+            .collect()
+    }
+
+    /// Stores the latest progress measured for a job
+    pub fn update_progress(&self, id: &str, progress: &DiskProgress) {
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.iter_mut().find(|r| r.id == id) {
+            record.last_progress = Some(RecordedProgress {
+                bytes_done: progress.bytes_done,
+                total_bytes: progress.total_bytes,
+                percentage: progress.percentage,
+                updated_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0),
+            });
+            self.persist(&records);
+        }
+    }
+
+    /// Stores whether a job's process is suspended
+    pub fn set_paused(&self, id: &str, paused: bool) {
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.iter_mut().find(|r| r.id == id) {
+            record.paused = paused;
+            self.persist(&records);
+        }
+    }
+
+    /// Flags a record as interrupted
+    fn mark_interrupted(&self, id: &str) {
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.iter_mut().find(|r| r.id == id) {
+            record.interrupted = true;
+            self.persist(&records);
+        }
+    }
+
+    /// Writes the records to disk
+    fn persist(&self, records: &[JobRecord]) {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| {
+                let content = serde_json::to_string_pretty(records)
+                    .map_err(std::io::Error::other)?;
+                fs::write(path, content)
+            });
+        if let Err(e) = result {
+            eprintln!("Failed to persist postcli job records to {:?}: {}", path, e);
+        }
+    }
+}
+
+fn create_output_file(path: &Path) -> Result<File, String> {
+    File::create(path).map_err(|e| format!("Failed to create postcli output {:?}: {}", path, e))
+}
+
+/// Re-attaches to PostCLI jobs that survived a restart of the application
+///
+/// Records whose process is still a live PostCLI are registered as attached jobs,
+/// as paused if they were paused when the application exited. All other active
+/// records are flagged as interrupted.
+///
+/// The attached jobs are not watched yet: their exit is only reported once
+/// they are passed to [`watch_recovered_jobs`], so that everything reacting to
+/// a job's exit, like the initialization queue, can be set up in between.
+///
+/// # Returns
+///
+/// * `Vec<(Arc<Job>, JobRecord)>` - The attached jobs with their records
+pub fn recover_jobs(app: &tauri::AppHandle) -> Vec<(Arc<Job>, JobRecord)> {
+    let store = app.state::<JobStore>();
+    let registry = app.state::<JobRegistry>();
+    let mut recovered = Vec::new();
+
+    for record in store.filter(|r| !r.interrupted) {
+        if process::is_postcli_process(record.pid) {
+            println!("Re-attaching to postcli process {} ({})", record.pid, record.id);
+            let job = registry.attach(record.pid, record.args.clone(), record.started_at);
+            if record.paused {
+                job.restore_paused();
+            }
+            recovered.push((job, record));
+        } else {
+            println!("postcli job {} was interrupted", record.id);
+            store.mark_interrupted(&record.id);
+        }
+    }
+    recovered
+}
+
+/// Starts watching the jobs returned by [`recover_jobs`]
+///
+/// Their data directory is monitored for progress, their output files are followed
+/// from their current end and their exit is detected by polling.
+pub fn watch_recovered_jobs(app: &tauri::AppHandle, recovered: Vec<(Arc<Job>, JobRecord)>) {
+    for (job, record) in recovered {
+        let layout = PosLayout::from_args(&record.args);
+        monitor::start_disk_monitor(app.clone(), job.clone(), layout, monitor::MONITOR_INTERVAL);
+        let readers = match &record.output {
+            Some(output) => {
+                let log = app.try_state::<JobLogs>().and_then(|logs| logs.create(&job));
+                postcli::spawn_output_readers(app.clone(), job.clone(), log, output, true)
+            }
+            None => Vec::new(),
+        };
+        jobs::watch_job(app.clone(), job, readers);
+    }
+}
@@ -0,0 +1,488 @@
+//! PostCLI Job Registry Module
+//!
+//! This module keeps track of the detached PostCLI processes started by the application.
+//! Every spawned `Child` is owned by the Tauri-managed [`JobRegistry`], keyed by a job id,
+//! and a background watcher thread waits on it so the application learns when and how
+//! PostCLI exited. Processes left running by a previous session can be attached to the
+//! registry by PID; their exit is detected by polling, without an exit status.
+//! Only the most recent exited jobs are kept, so the registry does not grow for
+//! the whole session.
+//!
+//! # Events
+//!
+//! * 'postcli-state' - [`JobStateChange`] payload whenever a job is paused or resumed
+//! * 'postcli-exit' - [`JobExit`] payload once a job's process has exited
+
+use std::collections::{HashMap, VecDeque};
+use std::process::{Child, ExitStatus};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde::Serialize;
+use tauri::Manager;
+
+use super::job_store::{self, JobStore};
+use super::process;
+use super::queue;
+
+/// Identifier of a job in the registry
+pub type JobId = u64;
+
+/// Number of stderr lines kept per job for the exit report
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Interval between checks of a running process for exit
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Number of exited jobs kept in the registry, older ones are pruned
+const MAX_EXITED_JOBS: usize = 20;
+
+/// Lifecycle state of a registered job
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum JobStatus {
+    /// The process is still running
+    Running,
+    /// The process is suspended and can be resumed
+    Paused,
+    /// The process has exited and was reaped
+    Exited {
+        /// Exit code, if the process exited normally
+        code: Option<i32>,
+        /// Terminating signal, if the process was killed by one (Unix only)
+        signal: Option<i32>,
+    },
+}
+
+/// Payload of the 'postcli-state' event
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStateChange {
+    pub job_id: JobId,
+    pub status: JobStatus,
+}
+
+/// Payload of the 'postcli-exit' event
+#[derive(Debug, Clone, Serialize)]
+pub struct JobExit {
+    pub job_id: JobId,
+    pub process_id: u32,
+    pub code: Option<i32>,
+    pub signal: Option<i32>,
+    /// Time between spawning and reaping the process, in seconds
+    pub runtime_secs: f64,
+    /// Last lines PostCLI wrote to stderr
+    pub stderr_tail: Vec<String>,
+}
+
+/// Snapshot of a job returned by `list_postcli_jobs`
+#[derive(Debug, Serialize)]
+pub struct JobInfo {
+    pub job_id: JobId,
+    pub process_id: u32,
+    pub args: Vec<String>,
+    /// Start time as seconds since the Unix epoch
+    pub started_at: u64,
+    pub runtime_secs: f64,
+    pub status: JobStatus,
+}
+
+struct JobState {
+    status: JobStatus,
+    runtime: Option<Duration>,
+    /// Start of the current pause, if the job is paused
+    paused_since: Option<Instant>,
+    /// Time spent in completed pauses
+    paused_total: Duration,
+}
+
+/// A PostCLI process owned by the registry
+pub struct Job {
+    pub id: JobId,
+    pub pid: u32,
+    pub args: Vec<String>,
+    started_at: SystemTime,
+    started: Instant,
+    /// The spawned process, `None` for processes attached by PID
+    child: Option<Mutex<Child>>,
+    state: Mutex<JobState>,
+    exited: Condvar,
+    stderr_tail: Mutex<VecDeque<String>>,
+}
+
+impl Job {
+    /// Returns the current status of the job
+    pub fn status(&self) -> JobStatus {
+        self.state.lock().unwrap().status.clone()
+    }
+
+    /// Returns whether the process has not been reaped yet (running or paused)
+    pub fn is_alive(&self) -> bool {
+        !matches!(self.status(), JobStatus::Exited { .. })
+    }
+
+    /// Blocks until the job's process has been reaped or the timeout elapses
+    ///
+    /// # Returns
+    ///
+    /// * `Some(JobStatus)` - The exit status if the process exited in time
+    /// * `None` - If the process is still alive after the timeout
+    pub fn wait_for_exit(&self, timeout: Duration) -> Option<JobStatus> {
+        let state = self.state.lock().unwrap();
+        let (state, _) = self
+            .exited
+            .wait_timeout_while(state, timeout, |s| !matches!(s.status, JobStatus::Exited { .. }))
+            .unwrap();
+        match state.status {
+            JobStatus::Exited { .. } => Some(state.status.clone()),
+            _ => None,
+        }
+    }
+
+    /// Switches the job between running and paused
+    ///
+    /// `signal` performs the actual suspension or resumption of the process. It is
+    /// only called if the job is in the opposite state, and the tracked state only
+    /// changes if it succeeds. On success the state is stored in the job's record,
+    /// so it survives a restart of the application, and the 'postcli-state' event
+    /// is emitted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the job was paused or resumed
+    /// * `Err(String)` - If the job is not in the opposite state or signalling failed
+    pub fn set_paused<F>(&self, app: &tauri::AppHandle, paused: bool, signal: F) -> Result<(), String>
+    where
+        F: FnOnce(u32) -> Result<(), String>,
+    {
+        let status = {
+            let mut state = self.state.lock().unwrap();
+            let next = match (&state.status, paused) {
+                (JobStatus::Running, true) => JobStatus::Paused,
+                (JobStatus::Paused, false) => JobStatus::Running,
+                (JobStatus::Exited { .. }, _) => {
+                    return Err(format!("Job {} has already exited", self.id))
+                }
+                (_, true) => return Err(format!("Job {} is already paused", self.id)),
+                (_, false) => return Err(format!("Job {} is not paused", self.id)),
+            };
+            signal(self.pid)?;
+            if paused {
+                state.paused_since = Some(Instant::now());
+            } else if let Some(since) = state.paused_since.take() {
+                state.paused_total += since.elapsed();
+            }
+            state.status = next.clone();
+            next
+        };
+
+        if let Some(store) = app.try_state::<JobStore>() {
+            store.set_paused(&job_store::record_id(self), paused);
+        }
+        let change = JobStateChange { job_id: self.id, status };
+        if let Err(e) = app.emit_all("postcli-state", change) {
+            eprintln!("Failed to emit postcli-state event: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Marks a re-attached job whose process was suspended before a restart as paused
+    ///
+    /// The process is not signalled. Time it spent paused before the restart is
+    /// not known and counts as active time.
+    pub fn restore_paused(&self) {
+        let mut state = self.state.lock().unwrap();
+        if matches!(state.status, JobStatus::Running) {
+            state.status = JobStatus::Paused;
+            state.paused_since = Some(Instant::now());
+        }
+    }
+
+    /// Time the process has been running, excluding the time it spent paused
+    pub fn active_time(&self) -> Duration {
+        let state = self.state.lock().unwrap();
+        let runtime = state.runtime.unwrap_or_else(|| self.started.elapsed());
+        let paused = state.paused_total + state.paused_since.map_or(Duration::ZERO, |since| since.elapsed());
+        runtime.saturating_sub(paused)
+    }
+
+    /// Start time as seconds since the Unix epoch
+    pub fn started_at_secs(&self) -> u64 {
+        self.started_at
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Forcibly terminates the process using the TerminateProcess API
+    #[cfg(windows)]
+    pub fn terminate(&self) -> Result<(), String> {
+        match &self.child {
+            Some(child) => child
+                .lock()
+                .unwrap()
+                .kill()
+                .map_err(|e| format!("Failed to terminate process {}: {}", self.pid, e)),
+            None => process::terminate_process(self.pid),
+        }
+    }
+
+    /// Records a line written by PostCLI to stderr
+    pub fn push_stderr(&self, line: &str) {
+        let mut tail = self.stderr_tail.lock().unwrap();
+        if tail.len() == STDERR_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line.to_string());
+    }
+
+    /// Builds a serializable snapshot of the job
+    pub fn info(&self) -> JobInfo {
+        let state = self.state.lock().unwrap();
+        let runtime = state.runtime.unwrap_or_else(|| self.started.elapsed());
+        JobInfo {
+            job_id: self.id,
+            process_id: self.pid,
+            args: self.args.clone(),
+            started_at: self.started_at_secs(),
+            runtime_secs: runtime.as_secs_f64(),
+            status: state.status.clone(),
+        }
+    }
+
+    /// Marks the job as exited
+    pub(crate) fn finish(&self, exit: Option<ExitStatus>) {
+        let runtime = self.started.elapsed();
+        let code = exit.and_then(|s| s.code());
+        let signal = exit.and_then(exit_signal);
+
+        let mut state = self.state.lock().unwrap();
+        state.status = JobStatus::Exited { code, signal };
+        state.runtime = Some(runtime);
+        if let Some(since) = state.paused_since.take() {
+            state.paused_total += since.elapsed();
+        }
+        self.exited.notify_all();
+    }
+
+    /// Builds the exit report of a finished job
+    pub fn exit_report(&self) -> JobExit {
+        let state = self.state.lock().unwrap();
+        let (code, signal) = match state.status {
+            JobStatus::Exited { code, signal } => (code, signal),
+            _ => (None, None),
+        };
+        JobExit {
+            job_id: self.id,
+            process_id: self.pid,
+            code,
+            signal,
+            runtime_secs: state.runtime.unwrap_or_else(|| self.started.elapsed()).as_secs_f64(),
+            stderr_tail: self.stderr_tail.lock().unwrap().iter().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn exit_signal(status: ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+/// Tauri-managed registry of PostCLI jobs keyed by job id
+#[derive(Default)]
+pub struct JobRegistry {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<JobId, Arc<Job>>>,
+}
+
+impl JobRegistry {
+    /// Takes ownership of a freshly spawned PostCLI process and assigns it a job id
+    pub fn register(&self, child: Child, args: Vec<String>) -> Arc<Job> {
+        let pid = child.id();
+        self.insert(pid, Some(child), args, SystemTime::now())
+    }
+
+    /// Registers a running PostCLI process that was not spawned in this session
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - Process ID of the PostCLI process
+    /// * `args` - Arguments the process was started with
+    /// * `started_at` - Start time as seconds since the Unix epoch
+    pub fn attach(&self, pid: u32, args: Vec<String>, started_at: u64) -> Arc<Job> {
+        let started_at = UNIX_EPOCH + Duration::from_secs(started_at);
+        self.insert(pid, None, args, started_at)
+    }
+
+    fn insert(&self, pid: u32, child: Option<Child>, args: Vec<String>, started_at: SystemTime) -> Arc<Job> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let running_for = SystemTime::now().duration_since(started_at).unwrap_or_default();
+        let job = Arc::new(Job {
+            id,
+            pid,
+            args,
+            started_at,
+            started: Instant::now().checked_sub(running_for).unwrap_or_else(Instant::now),
+            child: child.map(Mutex::new),
+            state: Mutex::new(JobState {
+                status: JobStatus::Running,
+                runtime: None,
+                paused_since: None,
+                paused_total: Duration::ZERO,
+            }),
+            exited: Condvar::new(),
+            stderr_tail: Mutex::new(VecDeque::with_capacity(STDERR_TAIL_LINES)),
+        });
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.insert(id, job.clone());
+        prune_exited(&mut jobs);
+        job
+    }
+
+    /// Looks up a job by id
+    pub fn get(&self, id: JobId) -> Result<Arc<Job>, String> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown postcli job {}", id))
+    }
+
+    /// Returns the first job matching a predicate
+    pub fn find<F: Fn(&Job) -> bool>(&self, predicate: F) -> Option<Arc<Job>> {
+        self.jobs.lock().unwrap().values().find(|j| predicate(j)).cloned()
+    }
+
+    /// Returns snapshots of all known jobs ordered by job id
+    pub fn list(&self) -> Vec<JobInfo> {
+        let mut jobs: Vec<JobInfo> = self.jobs.lock().unwrap().values().map(|j| j.info()).collect();
+        jobs.sort_by_key(|j| j.job_id);
+        jobs
+    }
+}
+
+/// Drops the oldest exited jobs beyond [`MAX_EXITED_JOBS`]
+fn prune_exited(jobs: &mut HashMap<JobId, Arc<Job>>) {
+    let mut exited: Vec<JobId> = jobs
+        .values()
+        .filter(|job| !job.is_alive())
+        .map(|job| job.id)
+        .collect();
+    if exited.len() > MAX_EXITED_JOBS {
+        exited.sort_unstable();
+        for id in &exited[..exited.len() - MAX_EXITED_JOBS] {
+            jobs.remove(id);
+        }
+    }
+}
+
+/// Waits for a job's process to exit in the background
+///
+/// Once the process has been reaped and the job marked as exited, the watcher
+/// lets the output reader threads read the output files to their end, so that
+/// the stderr tail in the exit report is complete. It then drops the job's
+/// persisted record, emits the 'postcli-exit' event and lets the initialization
+/// queue start its next entry.
+pub fn watch_job(app: tauri::AppHandle, job: Arc<Job>, readers: Vec<JoinHandle<()>>) {
+    thread::spawn(move || {
+        let exit = match &job.child {
+            Some(child) => loop {
+                let result = child.lock().unwrap().try_wait();
+                match result {
+                    Ok(Some(status)) => break Some(status),
+                    Ok(None) => thread::sleep(WAIT_POLL_INTERVAL),
+                    Err(e) => {
+                        eprintln!("Failed to wait for postcli job {}: {}", job.id, e);
+                        break None;
+                    }
+                }
+            },
+            // Attached processes are not our children, so only their liveness is known
+            None => {
+                while process::process_alive(job.pid) {
+                    thread::sleep(WAIT_POLL_INTERVAL);
+                }
+                None
+            }
+        };
+
+        job.finish(exit);
+        for reader in readers {
+            let _ = reader.join();
+        }
+
+        let report = job.exit_report();
+        if let Some(store) = app.try_state::<JobStore>() {
+            store.remove(&job_store::record_id(&job));
+        }
+        println!(
+            "postcli job {} exited (code: {:?}, signal: {:?}) after {:.1}s",
+            report.job_id, report.code, report.signal, report.runtime_secs
+        );
+        if let Err(e) = app.emit_all("postcli-exit", report.clone()) {
+            eprintln!("Failed to emit postcli-exit event: {}", e);
+        }
+        queue::on_job_exit(&app, &report);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prunes_oldest_exited_jobs() {
+        let registry = JobRegistry::default();
+        let running = registry.attach(1, Vec::new(), 0);
+        for _ in 0..MAX_EXITED_JOBS + 5 {
+            registry.attach(2, Vec::new(), 0).finish(None);
+        }
+        // Pruning happens on the next registration
+        let latest = registry.attach(3, Vec::new(), 0);
+
+        let ids: Vec<JobId> = registry.list().iter().map(|job| job.job_id).collect();
+        assert_eq!(ids.len(), MAX_EXITED_JOBS + 2);
+        assert!(ids.contains(&running.id));
+        assert!(ids.contains(&latest.id));
+        assert!(registry.get(running.id + 1).is_err());
+        assert!(registry.get(latest.id - 1).is_ok());
+    }
+
+    #[test]
+    fn excludes_paused_time_from_active_time() {
+        let registry = JobRegistry::default();
+        let started_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() - 100;
+        let job = registry.attach(1, Vec::new(), started_at);
+        {
+            let mut state = job.state.lock().unwrap();
+            state.paused_total = Duration::from_secs(30);
+            state.paused_since = Instant::now().checked_sub(Duration::from_secs(10));
+        }
+        let active = job.active_time().as_secs_f64();
+        assert!((59.0..=62.0).contains(&active), "active time {}", active);
+
+        job.finish(None);
+        let active = job.active_time().as_secs_f64();
+        assert!((59.0..=62.0).contains(&active), "active time {}", active);
+    }
+
+    #[test]
+    fn restores_paused_state() {
+        let registry = JobRegistry::default();
+        let job = registry.attach(1, Vec::new(), 0);
+        job.restore_paused();
+        assert!(matches!(job.status(), JobStatus::Paused));
+        assert!(job.state.lock().unwrap().paused_since.is_some());
+
+        job.finish(None);
+        job.restore_paused();
+        assert!(!job.is_alive());
+    }
+}
@@ -0,0 +1,257 @@
+//! PostCLI Log Parser Module
+//!
+//! This module turns raw PostCLI log lines into typed progress events. PostCLI logs
+//! through zap in console format, i.e. a timestamp, a level, a message and an optional
+//! trailing JSON object with structured fields:
+//!
+//! ```text
+//! 2024-12-17T22:34:57.991+0100    INFO    initialization: completed       {"fileIndex": 5}
+//! ```
+//!
+//! Lines that do not carry any progress information are ignored by the parser.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// A typed event extracted from a single PostCLI log line
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PostcliEvent {
+    /// PostCLI started writing a file, or continued writing a partially written one
+    FileStarted { file_index: u32, resumed: bool },
+    /// PostCLI finished a file, or found it already initialized
+    FileCompleted { file_index: u32, already_initialized: bool },
+    /// A nonce was found while generating the data
+    NonceFound { nonce: Option<u64> },
+    /// The whole initialization finished successfully
+    Completed,
+    /// A non-fatal warning
+    Warning { message: String },
+    /// An error that stops the initialization
+    Fatal { message: String, cause: Option<String> },
+}
+
+/// Log levels as printed by zap
+const LEVELS: [&str; 7] = ["DEBUG", "INFO", "WARN", "WARNING", "ERROR", "FATAL", "PANIC"];
+
+/// Parses a single PostCLI log line into a typed event
+///
+/// # Arguments
+///
+/// * `line` - Raw log line as written by PostCLI to stdout or stderr
+///
+/// # Returns
+///
+/// * `Some(PostcliEvent)` - If the line carries progress or error information
+/// * `None` - For any other line
+pub fn parse_line(line: &str) -> Option<PostcliEvent> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    let (text, fields) = split_fields(line);
+    let (level, message) = split_level(text);
+    let lower = message.to_lowercase();
+
+    // Errors first, so that a failing file is not reported as progress
+    let is_fatal = matches!(level, Some("ERROR" | "FATAL" | "PANIC"))
+        || lower.contains("aborting")
+        || (message.contains("WARNING")
+            && (message.contains("commitmentAtxId") || lower.contains("cannot proceed")))
+        || (level.is_none() && lower.contains("error"));
+    if is_fatal {
+        return Some(PostcliEvent::Fatal {
+            message: message.to_string(),
+            cause: fatal_cause(message, &fields),
+        });
+    }
+
+    let file_index = fields
+        .get("fileIndex")
+        .and_then(Value::as_u64)
+        .map(|i| i as u32);
+
+    if lower.contains("found nonce") {
+        return Some(PostcliEvent::NonceFound {
+            nonce: fields.get("nonce").and_then(Value::as_u64),
+        });
+    }
+    if lower.contains("cli: initialization completed") {
+        return Some(PostcliEvent::Completed);
+    }
+    if let Some(file_index) = file_index {
+        if message.starts_with("initialization: starting to write file") {
+            return Some(PostcliEvent::FileStarted { file_index, resumed: false });
+        }
+        if message.starts_with("initialization: continuing to write file") {
+            return Some(PostcliEvent::FileStarted { file_index, resumed: true });
+        }
+        if message.starts_with("initialization: completed") {
+            return Some(PostcliEvent::FileCompleted { file_index, already_initialized: false });
+        }
+        if message.starts_with("initialization: file already initialized") {
+            return Some(PostcliEvent::FileCompleted { file_index, already_initialized: true });
+        }
+    }
+
+    if matches!(level, Some("WARN" | "WARNING")) || message.contains("WARNING") {
+        return Some(PostcliEvent::Warning { message: message.to_string() });
+    }
+
+    None
+}
+
+/// Returns the log level of a PostCLI log line, if it has one
+pub fn line_level(line: &str) -> Option<&'static str> {
+    split_level(split_fields(line).0).0
+}
+
+/// Splits the trailing JSON object with structured fields off a log line
+fn split_fields(line: &str) -> (&str, Map<String, Value>) {
+    if line.ends_with('}') {
+        if let Some(start) = line.find('{') {
+            if let Ok(Value::Object(fields)) = serde_json::from_str(&line[start..]) {
+                return (line[..start].trim_end(), fields);
+            }
+        }
+    }
+    (line, Map::new())
+}
+
+/// Finds the log level and returns it together with the message following it
+fn split_level(text: &str) -> (Option<&'static str>, &str) {
+    let mut rest = text;
+    // The level is preceded by at most the timestamp (zap) or date and time (Go log)
+    for _ in 0..3 {
+        let token_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let token = rest[..token_end].trim_end_matches(':');
+        if let Some(level) = LEVELS.iter().find(|l| **l == token) {
+            return (Some(*level), rest[token_end..].trim());
+        }
+        if token_end == rest.len() {
+            break;
+        }
+        rest = rest[token_end..].trim_start();
+    }
+
+    // No level found, strip a leading timestamp if present
+    let message = match text.split_once(char::is_whitespace) {
+        Some((first, rest)) if first.starts_with(|c: char| c.is_ascii_digit()) => {
+            strip_time(rest.trim_start())
+        }
+        _ => text,
+    };
+    (None, message)
+}
+
+/// Strips the time part of a Go `log` timestamp ("2024/12/17 22:34:57 message")
+fn strip_time(text: &str) -> &str {
+    match text.split_once(char::is_whitespace) {
+        Some((first, rest)) if first.contains(':') && first.starts_with(|c: char| c.is_ascii_digit()) => {
+            rest.trim_start()
+        }
+        _ => text,
+    }
+}
+
+/// Determines a user-facing cause for a fatal error
+fn fatal_cause(message: &str, fields: &Map<String, Value>) -> Option<String> {
+    if message.contains("commitmentAtxId") {
+        return Some(
+            "Directory was previously initialized with a different ATX ID".to_string(),
+        );
+    }
+    if let Some(error) = fields.get("error").and_then(Value::as_str) {
+        return Some(error.to_string());
+    }
+    message
+        .rsplit_once(": ")
+        .map(|(_, cause)| cause.trim().to_string())
+        .filter(|cause| !cause.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_file_start_and_resume() {
+        let line = r#"2024-12-17T22:34:57.991+0100	INFO	initialization: starting to write file	{"fileIndex": 0, "currentNumLabels": 0, "targetNumLabels": 268435456}"#;
+        assert_eq!(
+            parse_line(line),
+            Some(PostcliEvent::FileStarted { file_index: 0, resumed: false })
+        );
+
+        let line = r#"2024-12-17T22:34:57.991+0100	INFO	initialization: continuing to write file	{"fileIndex": 3, "currentNumLabels": 1024}"#;
+        assert_eq!(
+            parse_line(line),
+            Some(PostcliEvent::FileStarted { file_index: 3, resumed: true })
+        );
+    }
+
+    #[test]
+    fn parses_file_completion() {
+        let line = r#"2024-12-17T22:34:57.991+0100    INFO    initialization: completed       {"fileIndex": 5}"#;
+        assert_eq!(
+            parse_line(line),
+            Some(PostcliEvent::FileCompleted { file_index: 5, already_initialized: false })
+        );
+
+        let line = r#"2024-12-17T22:34:57.991+0100    INFO    initialization: file already initialized       {"fileIndex": 2}"#;
+        assert_eq!(
+            parse_line(line),
+            Some(PostcliEvent::FileCompleted { file_index: 2, already_initialized: true })
+        );
+    }
+
+    #[test]
+    fn parses_nonce_and_completion() {
+        let line = r#"2024-12-17T22:34:57.991+0100	INFO	initialization: completed, found nonce	{"nonce": 117}"#;
+        assert_eq!(parse_line(line), Some(PostcliEvent::NonceFound { nonce: Some(117) }));
+
+        let line = "2024/12/17 22:34:58 cli: initialization completed";
+        assert_eq!(parse_line(line), Some(PostcliEvent::Completed));
+    }
+
+    #[test]
+    fn parses_fatal_errors_with_cause() {
+        let line = r#"2024-12-17T22:34:57.991+0100	ERROR	initialization: failed to write file	{"fileIndex": 4, "error": "no space left on device"}"#;
+        assert_eq!(
+            parse_line(line),
+            Some(PostcliEvent::Fatal {
+                message: "initialization: failed to write file".to_string(),
+                cause: Some("no space left on device".to_string()),
+            })
+        );
+
+        let line = "2024/12/17 22:34:57 WARNING: it appears that /data was previously initialized with a different `commitmentAtxId` value. aborting";
+        match parse_line(line) {
+            Some(PostcliEvent::Fatal { cause, .. }) => assert_eq!(
+                cause.as_deref(),
+                Some("Directory was previously initialized with a different ATX ID")
+            ),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_warnings() {
+        let line = "2024-12-17T22:34:57.991+0100	WARN	initialization: numUnits is lower than recommended";
+        assert_eq!(
+            parse_line(line),
+            Some(PostcliEvent::Warning {
+                message: "initialization: numUnits is lower than recommended".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_lines() {
+        assert_eq!(parse_line(""), None);
+        assert_eq!(
+            parse_line("2024-12-17T22:34:57.991+0100	INFO	initialization: using provider	{\"provider\": 0}"),
+            None
+        );
+    }
+}
@@ -4,15 +4,54 @@
 //! It exports various submodules that provide different functionalities:
 //! 
 //! - `file_dialog`: Handles directory selection and validation operations
+//! - `disk`: Queries the filesystem holding a directory, e.g. for its free space
+//! - `disk_benchmark`: Measures the throughput of the disk holding a directory
 //! - `postcli`: Manages interactions with the post-processing CLI tool
+//! - `calls`: Runs short PostCLI calls with a timeout and cancellation
+//! - `config`: Typed PostCLI configuration rendered to command-line arguments
+//! - `queue`: Persistent queue of initializations run one after another
+//! - `split`: Splits one initialization across several providers running in parallel
+//! - `jobs`: Keeps a registry of detached PostCLI processes and reports their exit status
+//! - `job_store`: Persists running jobs and recovers them after an application restart
+//! - `job_logs`: Writes per-job PostCLI log files and retrieves their content
+//! - `process`: Platform-specific helpers for processes identified by PID
+//! - `log_parser`: Parses PostCLI log lines into typed progress events
+//! - `pos_data`: Describes the on-disk layout of PoS data directories
+//! - `pos_metadata`: Reads and checks the metadata file of PoS data directories
+//! - `pos_inspect`: Reports the state of every data file in a PoS data directory
+//! - `monitor`: Tracks initialization progress by watching the data directory
 //! - `cpu`: Provides CPU-related functionality and information
 //! - `profiler`: Implements profiling and performance analysis features
+//! - `profiler_history`: Persists profiler results with the hardware they were measured on
+//! - `proving`: Models the probability of finding a PoST proof in one pass
+//! - `optimizer`: Recommends proving nonces and threads from profiler measurements
 //! 
 //! Each submodule contains specific Tauri commands that can be invoked from the frontend
 //! to perform various system-level operations.
 
 pub mod file_dialog;
+pub mod disk;
+pub mod disk_benchmark;
 pub mod postcli;
+pub mod calls;
+pub mod config;
+pub mod queue;
+pub mod split;
+pub mod jobs;
+pub mod job_store;
+pub mod job_logs;
+pub mod process;
+pub mod log_parser;
+pub mod pos_data;
+pub mod pos_metadata;
+pub mod pos_inspect;
+pub mod monitor;
 pub mod cpu;
 pub mod profiler;
-pub mod fs;
\ No newline at end of file
+pub mod profiler_history;
+pub mod proving;
+pub mod optimizer;
+pub mod fs;
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,190 @@
+//! PoS Directory Monitor Module
+//!
+//! This module tracks the progress of a running initialization by examining the
+//! data directory instead of scraping PostCLI logs. While a job is running, the
+//! monitor periodically sums the sizes of the `postdata_N.bin` files, compares them
+//! with the size expected from the job's parameters and reports bytes written,
+//! completion percentage, write rates and the estimated time remaining.
+//!
+//! # Events
+//!
+//! Emits 'postcli-disk-progress' events with a [`DiskProgress`] payload.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use serde::Serialize;
+use tauri::Manager;
+
+use super::job_store::{self, JobStore};
+use super::jobs::{Job, JobId};
+use super::pos_data::{self, PosLayout};
+
+/// Interval between two scans of the data directory
+pub const MONITOR_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Payload of the 'postcli-disk-progress' event
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskProgress {
+    pub job_id: JobId,
+    /// Bytes of PoS data present in the data directory
+    pub bytes_done: u64,
+    /// Bytes of PoS data of the complete initialization, or of the job's file range
+    pub total_bytes: u64,
+    /// Completion percentage (0-100)
+    pub percentage: f64,
+    /// Index of the first file that is not fully written, `None` once all files are complete
+    pub current_file: Option<u32>,
+    /// Number of files within the job's file range
+    pub total_files: u32,
+    /// Write rate since the previous scan in bytes per second
+    pub write_rate: f64,
+    /// Average write rate since the monitor started in bytes per second, excluding paused time
+    pub average_rate: f64,
+    /// Estimated time remaining in seconds, based on the average write rate
+    pub eta_secs: Option<f64>,
+}
+
+/// Sums the bytes written so far and finds the file currently being written
+///
+/// Only files within the layout's file range are considered. Each file only
+/// counts up to its expected size, so that stray oversized files cannot push
+/// the progress beyond 100%.
+pub fn scan_progress(layout: &PosLayout) -> (u64, Option<u32>) {
+    let files = pos_data::list_data_files(&layout.datadir).unwrap_or_default();
+    let (from, to) = layout.file_range();
+
+    let mut bytes_done = 0;
+    let mut current_file = None;
+    for index in from..=to {
+        let expected = layout.expected_file_size(index);
+        let size = files
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, size)| (*size).min(expected))
+            .unwrap_or(0);
+        bytes_done += size;
+        if size < expected && current_file.is_none() {
+            current_file = Some(index);
+        }
+    }
+    (bytes_done, current_file)
+}
+
+/// Starts monitoring the data directory of a running job
+///
+/// The monitor runs in a background thread until the job exits and emits one
+/// final sample afterwards, so that listeners see the end state of the directory.
+/// Every sample is also stored as the last progress of the job's persisted record.
+///
+/// # Arguments
+///
+/// * `app` - Tauri application handle for event emission
+/// * `job` - Job whose data directory is monitored
+/// * `layout` - Expected layout of the initialization
+/// * `interval` - Time between two scans
+pub fn start_disk_monitor(app: tauri::AppHandle, job: Arc<Job>, layout: PosLayout, interval: Duration) {
+    thread::spawn(move || {
+        let total_bytes = layout.range_bytes();
+        let (from, to) = layout.file_range();
+        let record_id = job_store::record_id(&job);
+        // Rates are measured against the job's active time, so pauses do not dilute them
+        let started = job.active_time();
+        let (initial_bytes, _) = scan_progress(&layout);
+        let mut last_bytes = initial_bytes;
+        let mut last_scan = started;
+
+        loop {
+            let running = job.is_alive();
+            if running {
+                thread::sleep(interval);
+            }
+
+            let (bytes_done, current_file) = scan_progress(&layout);
+            let now = job.active_time();
+            let write_rate = rate(bytes_done.saturating_sub(last_bytes), now.saturating_sub(last_scan));
+            let average_rate = rate(bytes_done.saturating_sub(initial_bytes), now.saturating_sub(started));
+            let remaining = total_bytes.saturating_sub(bytes_done);
+            last_bytes = bytes_done;
+            last_scan = now;
+
+            let progress = DiskProgress {
+                job_id: job.id,
+                bytes_done,
+                total_bytes,
+                percentage: if total_bytes > 0 {
+                    bytes_done as f64 / total_bytes as f64 * 100.0
+                } else {
+                    0.0
+                },
+                current_file,
+                total_files: (to + 1).saturating_sub(from),
+                write_rate,
+                average_rate,
+                eta_secs: (average_rate > 0.0).then(|| remaining as f64 / average_rate),
+            };
+            if let Some(store) = app.try_state::<JobStore>() {
+                store.update_progress(&record_id, &progress);
+            }
+            if let Err(e) = app.emit_all("postcli-disk-progress", progress) {
+                eprintln!("Failed to emit postcli-disk-progress event: {}", e);
+            }
+
+            if !running {
+                break;
+            }
+        }
+    });
+}
+
+/// Converts a byte count over a time span into bytes per second
+fn rate(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 {
+        bytes as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::data_dir;
+
+    fn layout(datadir: std::path::PathBuf) -> PosLayout {
+        PosLayout {
+            datadir,
+            num_units: 4,
+            labels_per_unit: 1024,
+            max_file_size: 16 * 1024,
+            from_file: None,
+            to_file: None,
+        }
+    }
+
+    #[test]
+    fn scans_written_bytes() {
+        let dir = data_dir("monitor-scan", &[16 * 1024, 1000]);
+        let (bytes_done, current_file) = scan_progress(&layout(dir.clone()));
+        assert_eq!(bytes_done, 16 * 1024 + 1000);
+        assert_eq!(current_file, Some(1));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn scans_file_range_and_clamps_oversized_files() {
+        let dir = data_dir("monitor-range", &[16 * 1024, 20 * 1024, 16 * 1024, 16 * 1024]);
+        let mut layout = layout(dir.clone());
+        layout.from_file = Some(1);
+        layout.to_file = Some(2);
+        assert_eq!(scan_progress(&layout), (2 * 16 * 1024, None));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn computes_rates() {
+        assert_eq!(rate(1000, Duration::from_secs(4)), 250.0);
+        assert_eq!(rate(1000, Duration::ZERO), 0.0);
+    }
+}
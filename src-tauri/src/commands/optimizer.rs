@@ -0,0 +1,251 @@
+//! Proving Optimizer Module
+//!
+//! This module recommends the number of nonces and threads used for proving.
+//! It runs the profiler over a grid of nonce and thread counts and estimates for
+//! every point the chance of finding a proof within the safe part of the cycle
+//! gap, see [`super::proving`].
+//!
+//! More nonces raise the chance of a proof per pass but slow every pass down, so
+//! neither the fastest point nor the one with the most nonces is necessarily
+//! the best. Nonce counts are multiples of 16, as PostCLI requires, and thread
+//! counts never exceed the CPU cores left after reserving a share for the node
+//! and the rest of the system.
+//!
+//! # Events
+//!
+//! Emits the 'profiler-progress' and 'profiler-sweep-progress' events of a
+//! profiler sweep while the grid is measured.
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, State};
+
+use super::cpu::get_cpu_cores;
+use super::profiler::{run_points, ProfilerConfig, ProfilerResult, ProfilerRuns, SweepRange};
+use super::proving::{FeasibilityEstimate, FeasibilityInput, PostParams};
+
+/// Share of the CPU cores kept free if none is given
+pub const DEFAULT_RESERVED_CORES_SHARE: f64 = 0.25;
+
+/// Nonce counts measured if no range is given
+const DEFAULT_NONCES: SweepRange = SweepRange { start: 16, end: 256, step: 48 };
+
+/// Probabilities closer than this are considered equal when ranking points
+const PROBABILITY_EPSILON: f64 = 1e-9;
+
+/// Options of [`optimize_proving_config`]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OptimizerOptions {
+    /// Share of the CPU cores to keep free, [`DEFAULT_RESERVED_CORES_SHARE`] if omitted
+    pub reserved_cores_share: Option<f64>,
+    /// Nonce counts to measure, multiples of 16
+    pub nonces: Option<SweepRange>,
+    /// Read speed of the disk holding the data in MiB/s, the CPU alone is considered if omitted
+    pub disk_read_mib_s: Option<f64>,
+    /// Network parameters, MainNet if omitted
+    pub params: Option<PostParams>,
+}
+
+/// Measured point with its proving estimate
+#[derive(Debug, Clone, Serialize)]
+pub struct ProvingCandidate {
+    pub result: ProfilerResult,
+    pub estimate: FeasibilityEstimate,
+}
+
+/// Result of [`optimize_proving_config`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ProvingRecommendation {
+    /// Logical CPU cores of the machine
+    pub cores: usize,
+    /// Cores kept free
+    pub reserved_cores: usize,
+    /// All measured points, ordered by nonces, then threads
+    pub candidates: Vec<ProvingCandidate>,
+    /// Point with the best chance of finding a proof in time
+    pub recommended: ProvingCandidate,
+}
+
+/// Recommends nonces and threads for proving
+///
+/// Profiles every combination of the nonce counts and a set of thread counts up
+/// to the usable cores, and picks the point with the highest probability of
+/// finding a proof before the deadline. Among equally likely points the one
+/// with the shorter expected proving time wins, then the one with fewer threads.
+///
+/// # Arguments
+///
+/// * `app` - Tauri application handle for resource access and progress events
+/// * `num_units` - Number of space units of the PoS data
+/// * `options` - Optional reserved share of cores, nonce range, disk speed and network parameters
+/// * `config` - Optional profiler configuration, shared by all points
+/// * `run_id` - Optional id under which the search can be cancelled with `cancel_profiler`,
+///   generated if omitted
+///
+/// # Returns
+///
+/// * `Ok(ProvingRecommendation)` - Recommended point and all measured candidates
+/// * `Err(String)` - If the options are invalid, a point fails or the search is cancelled
+///
+/// # Example
+///
+/// ```rust
+/// let recommendation = optimize_proving_config(app, 16, None, None, None, runs).await?;
+/// println!("Use {} nonces with {} threads",
+///     recommendation.recommended.result.nonces,
+///     recommendation.recommended.result.threads);
+/// ```
+#[command]
+pub async fn optimize_proving_config(
+    app: tauri::AppHandle,
+    num_units: u32,
+    options: Option<OptimizerOptions>,
+    config: Option<ProfilerConfig>,
+    run_id: Option<String>,
+    runs: State<'_, ProfilerRuns>,
+) -> Result<ProvingRecommendation, String> {
+    let options = options.unwrap_or_default();
+    let nonce_values = options.nonces.clone().unwrap_or(DEFAULT_NONCES).values();
+    if nonce_values.is_empty() || nonce_values.iter().any(|n| *n == 0 || n % 16 != 0) {
+        return Err("Nonces must be multiples of 16".to_string());
+    }
+    let cores = get_cpu_cores();
+    let reserved_cores = reserved_cores(
+        cores,
+        options.reserved_cores_share.unwrap_or(DEFAULT_RESERVED_CORES_SHARE),
+    )?;
+    let thread_values = thread_candidates(cores - reserved_cores);
+    // Checks the parameters before spending minutes on profiling
+    estimate(&options, num_units, 16, 1.0)?;
+
+    let points: Vec<(u32, u32)> = nonce_values
+        .iter()
+        .flat_map(|nonces| thread_values.iter().map(move |threads| (*nonces, *threads)))
+        .collect();
+    let results = run_points(app, points, config, run_id, &runs).await?;
+
+    let candidates = results
+        .into_iter()
+        .map(|result| {
+            let estimate = estimate(&options, num_units, result.nonces, result.speed_gib_s)?;
+            Ok(ProvingCandidate { result, estimate })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let recommended = best_candidate(&candidates)
+        .cloned()
+        .ok_or_else(|| "No profiler results to recommend from".to_string())?;
+
+    Ok(ProvingRecommendation {
+        cores,
+        reserved_cores,
+        candidates,
+        recommended,
+    })
+}
+
+/// Number of cores kept free, leaving at least one core for proving
+fn reserved_cores(cores: usize, share: f64) -> Result<usize, String> {
+    if !(0.0..1.0).contains(&share) {
+        return Err("Reserved share of cores must be at least 0 and below 1".to_string());
+    }
+    let reserved = (cores as f64 * share).ceil() as usize;
+    Ok(reserved.min(cores.saturating_sub(1)))
+}
+
+/// Thread counts measured for a number of usable cores
+///
+/// Powers of two below the usable cores and the usable cores themselves, as
+/// the speed rarely changes much between neighbouring thread counts.
+fn thread_candidates(usable_cores: usize) -> Vec<u32> {
+    let usable = usable_cores.max(1) as u32;
+    let mut threads: Vec<u32> = (0..32)
+        .map(|exp| 1u32 << exp)
+        .take_while(|threads| *threads < usable)
+        .collect();
+    threads.push(usable);
+    threads
+}
+
+/// Feasibility estimate of a measured point
+fn estimate(options: &OptimizerOptions, num_units: u32, nonces: u32, speed_gib_s: f64) -> Result<FeasibilityEstimate, String> {
+    FeasibilityInput {
+        num_units,
+        nonces,
+        cpu_speed_gib_s: Some(speed_gib_s),
+        disk_read_mib_s: options.disk_read_mib_s,
+        cycle_gap_hours: None,
+        safe_period: None,
+        target_probability: None,
+        params: options.params,
+    }
+    .estimate()
+}
+
+/// Picks the candidate most likely to find a proof in time
+fn best_candidate(candidates: &[ProvingCandidate]) -> Option<&ProvingCandidate> {
+    candidates.iter().fold(None, |best: Option<&ProvingCandidate>, candidate| match best {
+        Some(best) if !is_better(candidate, best) => Some(best),
+        _ => Some(candidate),
+    })
+}
+
+fn is_better(candidate: &ProvingCandidate, other: &ProvingCandidate) -> bool {
+    let (a, b) = (&candidate.estimate, &other.estimate);
+    if (a.probability_in_deadline - b.probability_in_deadline).abs() > PROBABILITY_EPSILON {
+        return a.probability_in_deadline > b.probability_in_deadline;
+    }
+    if a.expected_total_s != b.expected_total_s {
+        return a.expected_total_s < b.expected_total_s;
+    }
+    candidate.result.threads < other.result.threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(nonces: u32, threads: u32, speed_gib_s: f64) -> ProvingCandidate {
+        let options = OptimizerOptions::default();
+        ProvingCandidate {
+            result: ProfilerResult {
+                nonces,
+                threads,
+                time_s: 10.0,
+                speed_gib_s,
+                data_size: 1,
+                duration: 10,
+                data_file: None,
+                read_only: false,
+            },
+            estimate: estimate(&options, 4, nonces, speed_gib_s).unwrap(),
+        }
+    }
+
+    #[test]
+    fn reserves_share_of_cores() {
+        assert_eq!(reserved_cores(16, 0.25), Ok(4));
+        assert_eq!(reserved_cores(6, 0.25), Ok(2));
+        assert_eq!(reserved_cores(1, 0.5), Ok(0));
+        assert_eq!(reserved_cores(8, 0.0), Ok(0));
+        assert!(reserved_cores(8, 1.0).is_err());
+    }
+
+    #[test]
+    fn lists_thread_candidates() {
+        assert_eq!(thread_candidates(12), vec![1, 2, 4, 8, 12]);
+        assert_eq!(thread_candidates(8), vec![1, 2, 4, 8]);
+        assert_eq!(thread_candidates(0), vec![1]);
+    }
+
+    #[test]
+    fn recommends_most_likely_point() {
+        // 256 GiB at 0.01 GiB/s take 7.1 hours per pass: one pass fits, so more nonces win
+        let candidates = vec![candidate(16, 4, 0.01), candidate(64, 4, 0.01), candidate(64, 2, 0.005)];
+        let best = best_candidate(&candidates).unwrap();
+        assert_eq!((best.result.nonces, best.result.threads), (64, 4));
+
+        // Both are certain to finish in time, the faster one proves sooner
+        let candidates = vec![candidate(64, 8, 1.0), candidate(64, 4, 2.0), candidate(64, 2, 2.0)];
+        let best = best_candidate(&candidates).unwrap();
+        assert_eq!((best.result.nonces, best.result.threads), (64, 2));
+    }
+}
@@ -0,0 +1,234 @@
+//! PoS Data Layout Module
+//!
+//! This module describes how PostCLI lays out Proof of Space data on disk:
+//! `postdata_N.bin` files of at most `maxFileSize` bytes each, holding
+//! `numUnits * labelsPerUnit` labels of 16 bytes in total.
+//!
+//! It is shared by the commands that need to reason about the expected size
+//! of an initialization and about the files already present in a data directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Size of a single label in bytes (128 bits)
+pub const BYTES_PER_LABEL: u64 = 16;
+
+/// PostCLI default for `-labelsPerUnit` (MainNet)
+pub const DEFAULT_LABELS_PER_UNIT: u64 = 4_294_967_296;
+
+/// PostCLI default for `-maxFileSize` (4 GiB)
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+
+/// PostCLI default for `-numUnits`
+pub const DEFAULT_NUM_UNITS: u32 = 4;
+
+/// Expected layout of an initialization in a data directory
+#[derive(Debug, Clone)]
+pub struct PosLayout {
+    pub datadir: PathBuf,
+    pub num_units: u32,
+    pub labels_per_unit: u64,
+    pub max_file_size: u64,
+    /// First file to initialize (`-fromFile`), the first file of the data if `None`
+    pub from_file: Option<u32>,
+    /// Last file to initialize, inclusive (`-toFile`), the last file of the data if `None`
+    pub to_file: Option<u32>,
+}
+
+impl PosLayout {
+    /// Derives the layout from PostCLI command-line arguments
+    ///
+    /// Arguments that are not given fall back to the PostCLI defaults,
+    /// including the `~/post/data` data directory.
+    pub fn from_args(args: &[String]) -> PosLayout {
+        let value = |name: &str| arg_value(args, name);
+        PosLayout {
+            datadir: value("datadir")
+                .map(PathBuf::from)
+                .unwrap_or_else(default_datadir),
+            num_units: value("numUnits")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_NUM_UNITS),
+            labels_per_unit: value("labelsPerUnit")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_LABELS_PER_UNIT),
+            max_file_size: value("maxFileSize")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_FILE_SIZE),
+            from_file: value("fromFile").and_then(|v| v.parse().ok()),
+            to_file: value("toFile").and_then(|v| v.parse().ok()),
+        }
+    }
+
+    /// Total size of the initialized data in bytes
+    pub fn total_bytes(&self) -> u64 {
+        self.num_units as u64 * self.labels_per_unit * BYTES_PER_LABEL
+    }
+
+    /// Number of labels a single file can hold
+    fn labels_per_file(&self) -> u64 {
+        (self.max_file_size / BYTES_PER_LABEL).max(1)
+    }
+
+    /// Number of `postdata_N.bin` files of a complete initialization
+    pub fn num_files(&self) -> u32 {
+        let total_labels = self.num_units as u64 * self.labels_per_unit;
+        let labels_per_file = self.labels_per_file();
+        total_labels.div_ceil(labels_per_file) as u32
+    }
+
+    /// Expected size in bytes of the file with the given index
+    pub fn expected_file_size(&self, index: u32) -> u64 {
+        let file_bytes = self.labels_per_file() * BYTES_PER_LABEL;
+        let offset = index as u64 * file_bytes;
+        self.total_bytes().saturating_sub(offset).min(file_bytes)
+    }
+
+    /// Inclusive range of file indices initialized by this layout
+    ///
+    /// Without `-fromFile`/`-toFile` this covers all files of the data.
+    pub fn file_range(&self) -> (u32, u32) {
+        let last = self.num_files().saturating_sub(1);
+        (self.from_file.unwrap_or(0), self.to_file.unwrap_or(last).min(last))
+    }
+
+    /// Size in bytes of the files within [`PosLayout::file_range`]
+    pub fn range_bytes(&self) -> u64 {
+        let (from, to) = self.file_range();
+        (from..=to).map(|index| self.expected_file_size(index)).sum()
+    }
+
+    /// Bytes still to be written to complete the data, accounting for the files already present
+    pub fn remaining_bytes(&self) -> u64 {
+        let files = list_data_files(&self.datadir).unwrap_or_default();
+        (0..self.num_files())
+            .map(|index| {
+                let written = files.iter().find(|(i, _)| *i == index).map_or(0, |(_, size)| *size);
+                self.expected_file_size(index).saturating_sub(written)
+            })
+            .sum()
+    }
+
+    /// Index of the last file of the contiguous run of fully written files starting at 0
+    ///
+    /// # Returns
+    ///
+    /// * `Some(u32)` - Index of the last complete file
+    /// * `None` - If not even `postdata_0.bin` is complete
+    pub fn last_complete_file(&self) -> Option<u32> {
+        let files = list_data_files(&self.datadir).unwrap_or_default();
+        let mut last = None;
+        for index in 0..self.num_files() {
+            let complete = files
+                .iter()
+                .any(|(i, size)| *i == index && *size == self.expected_file_size(index));
+            if !complete {
+                break;
+            }
+            last = Some(index);
+        }
+        last
+    }
+}
+
+/// Extracts the index from a `postdata_N.bin` file name
+pub fn parse_data_file_name(name: &str) -> Option<u32> {
+    name.strip_prefix("postdata_")?
+        .strip_suffix(".bin")?
+        .parse()
+        .ok()
+}
+
+/// Lists the `postdata_N.bin` files in a directory with their sizes, ordered by index
+pub fn list_data_files(dir: &Path) -> std::io::Result<Vec<(u32, u64)>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if let Some(index) = name.to_str().and_then(parse_data_file_name) {
+            files.push((index, entry.metadata()?.len()));
+        }
+    }
+    files.sort_by_key(|(index, _)| *index);
+    Ok(files)
+}
+
+/// Looks up the value of a PostCLI flag given as `-name=value` or `--name=value`
+pub fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter().find_map(|arg| {
+        let (key, value) = arg.trim_start_matches('-').split_once('=')?;
+        if key == name {
+            Some(value)
+        } else {
+            None
+        }
+    })
+}
+
+/// Default PostCLI data directory (`~/post/data`)
+fn default_datadir() -> PathBuf {
+    tauri::api::path::home_dir()
+        .unwrap_or_default()
+        .join("post")
+        .join("data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::data_dir;
+
+    /// 4 units of 1000 labels in files of 1024 labels: 3 files of 16 KiB and one of 14,848 bytes
+    fn layout(datadir: PathBuf) -> PosLayout {
+        PosLayout::from_args(&[
+            format!("-datadir={}", datadir.display()),
+            "-numUnits=4".to_string(),
+            "-labelsPerUnit=1000".to_string(),
+            "--maxFileSize=16384".to_string(),
+        ])
+    }
+
+    #[test]
+    fn derives_layout_from_args() {
+        let layout = layout(PathBuf::from("/data"));
+        assert_eq!(layout.datadir, PathBuf::from("/data"));
+        assert_eq!(layout.total_bytes(), 64_000);
+        assert_eq!(layout.num_files(), 4);
+        assert_eq!(layout.expected_file_size(0), 16_384);
+        assert_eq!(layout.expected_file_size(3), 64_000 - 3 * 16_384);
+        assert_eq!(layout.expected_file_size(4), 0);
+        assert_eq!(layout.file_range(), (0, 3));
+
+        let defaults = PosLayout::from_args(&[]);
+        assert_eq!(defaults.num_units, DEFAULT_NUM_UNITS);
+        assert_eq!(defaults.num_files(), 64);
+    }
+
+    #[test]
+    fn limits_file_range() {
+        let mut layout = layout(PathBuf::from("/data"));
+        layout.from_file = Some(1);
+        layout.to_file = Some(9);
+        assert_eq!(layout.file_range(), (1, 3));
+        assert_eq!(layout.range_bytes(), 64_000 - 16_384);
+    }
+
+    #[test]
+    fn parses_data_file_names() {
+        assert_eq!(parse_data_file_name("postdata_12.bin"), Some(12));
+        assert_eq!(parse_data_file_name("postdata_x.bin"), None);
+        assert_eq!(parse_data_file_name("postdata_1.bin.tmp"), None);
+        assert_eq!(parse_data_file_name("postdata_metadata.json"), None);
+    }
+
+    #[test]
+    fn measures_files_present() {
+        let dir = data_dir("pos-data", &[16_384, 16_384, 100]);
+        fs::write(dir.join("postdata_metadata.json"), b"{}").unwrap();
+        let layout = layout(dir.clone());
+        assert_eq!(list_data_files(&dir).unwrap(), vec![(0, 16_384), (1, 16_384), (2, 100)]);
+        assert_eq!(layout.last_complete_file(), Some(1));
+        assert_eq!(layout.remaining_bytes(), 64_000 - 2 * 16_384 - 100);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
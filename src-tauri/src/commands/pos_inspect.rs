@@ -0,0 +1,310 @@
+//! PoS Directory Inspection Module
+//!
+//! This module inspects a PoS data directory in detail before an initialization
+//! is started or resumed in it. Every `postdata_N.bin` file is compared with the
+//! size the layout expects, missing, truncated and oversized files are flagged,
+//! and files that do not belong to an initialization are listed.
+//!
+//! The layout is taken from `postdata_metadata.json` when the directory has one,
+//! as PostCLI resumes with the parameters stored there, and from the configuration
+//! chosen in the application otherwise.
+
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+
+use super::config::PostCliConfig;
+use super::pos_data::{parse_data_file_name, PosLayout};
+use super::pos_metadata::{PostMetadata, METADATA_FILE};
+
+/// Key files that may be stored next to the PoS data
+pub const IDENTITY_KEY_FILE: &str = "identity.key";
+pub const KEY_BIN_FILE: &str = "key.bin";
+
+/// State of a single data file compared with the layout
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    Complete,
+    Missing,
+    /// Smaller than expected, PostCLI continues writing it
+    Truncated,
+    /// Larger than the layout allows, the data does not match the layout
+    Oversized,
+}
+
+/// Data file expected by the layout
+#[derive(Debug, Clone, Serialize)]
+pub struct DataFileReport {
+    pub index: u32,
+    /// Size on disk, `None` if the file is missing
+    pub size: Option<u64>,
+    pub expected_size: u64,
+    pub status: FileStatus,
+}
+
+/// Overall classification of a directory
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectoryState {
+    /// No data and no metadata, a new initialization starts here
+    Empty,
+    /// Initialization can be resumed
+    Partial,
+    /// All data files are written and the nonce was found
+    Complete,
+    /// The content does not match a single initialization and cannot be resumed safely
+    Inconsistent,
+}
+
+/// Where the expected layout of the directory was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutSource {
+    Metadata,
+    Config,
+}
+
+/// Result of [`inspect_pos_directory`]
+#[derive(Debug, Clone, Serialize)]
+pub struct PosDirectoryReport {
+    pub state: DirectoryState,
+    pub layout_source: LayoutSource,
+    /// Number of data files of a complete initialization
+    pub num_files: u32,
+    /// One entry per expected data file, ordered by index
+    pub files: Vec<DataFileReport>,
+    /// `postdata_N.bin` files beyond the last expected index
+    pub extra_data_files: Vec<String>,
+    /// Any other files not written by PostCLI
+    pub unexpected_files: Vec<String>,
+    pub has_metadata: bool,
+    /// Whether the metadata records the nonce of a finished initialization
+    pub has_nonce: bool,
+    /// Set if the metadata file exists but cannot be read
+    pub metadata_error: Option<String>,
+    pub has_identity_key: bool,
+    pub has_key_bin: bool,
+    /// First file that is not complete, where resuming continues
+    pub resume_from_file: Option<u32>,
+    /// Bytes still to be written to complete the data
+    pub remaining_bytes: u64,
+}
+
+/// Inspects the content of a PoS data directory
+///
+/// # Arguments
+///
+/// * `dir` - PoS data directory
+/// * `config` - Configuration chosen in the application, used if the directory has no metadata
+///
+/// # Returns
+///
+/// * `Ok(PosDirectoryReport)` - Per-file report and classification of the directory
+/// * `Err(String)` - If the directory cannot be read
+///
+/// # Example
+///
+/// ```rust
+/// let report = inspect_pos_directory("/path/to/post/data".to_string(), None)?;
+/// if let Some(index) = report.resume_from_file {
+///     println!("Resuming will continue with postdata_{}.bin", index);
+/// }
+/// ```
+#[tauri::command]
+pub fn inspect_pos_directory(dir: String, config: Option<PostCliConfig>) -> Result<PosDirectoryReport, String> {
+    inspect(Path::new(&dir), &config.unwrap_or_default())
+}
+
+/// Inspects a directory, see [`inspect_pos_directory`]
+fn inspect(dir: &Path, config: &PostCliConfig) -> Result<PosDirectoryReport, String> {
+    let mut data_files = Vec::new();
+    let mut other_files = Vec::new();
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        match parse_data_file_name(&name) {
+            Some(index) => {
+                let size = entry.metadata().map_err(|e| format!("Failed to read {}: {}", name, e))?.len();
+                data_files.push((index, name, size));
+            }
+            None => other_files.push(name),
+        }
+    }
+    data_files.sort_by_key(|(index, _, _)| *index);
+    other_files.sort();
+
+    let has_metadata = other_files.iter().any(|name| name == METADATA_FILE);
+    let metadata = if has_metadata { Some(PostMetadata::read(dir)) } else { None };
+    let (layout, layout_source) = match &metadata {
+        Some(Ok(metadata)) => (metadata.layout(dir), LayoutSource::Metadata),
+        _ => {
+            let mut layout = PosLayout::from_args(&config.to_args());
+            layout.datadir = dir.to_path_buf();
+            (layout, LayoutSource::Config)
+        }
+    };
+    let metadata_error = match &metadata {
+        Some(Err(e)) => Some(e.clone()),
+        _ => None,
+    };
+    let has_nonce = matches!(&metadata, Some(Ok(metadata)) if metadata.nonce.is_some());
+
+    let num_files = layout.num_files();
+    let files: Vec<DataFileReport> = (0..num_files)
+        .map(|index| {
+            let expected_size = layout.expected_file_size(index);
+            let size = data_files.iter().find(|(i, _, _)| *i == index).map(|(_, _, size)| *size);
+            let status = match size {
+                None => FileStatus::Missing,
+                Some(size) if size < expected_size => FileStatus::Truncated,
+                Some(size) if size > expected_size => FileStatus::Oversized,
+                Some(_) => FileStatus::Complete,
+            };
+            DataFileReport { index, size, expected_size, status }
+        })
+        .collect();
+    let extra_data_files: Vec<String> = data_files
+        .into_iter()
+        .filter(|(index, _, _)| *index >= num_files)
+        .map(|(_, name, _)| name)
+        .collect();
+    let unexpected_files: Vec<String> = other_files
+        .iter()
+        .filter(|name| ![METADATA_FILE, IDENTITY_KEY_FILE, KEY_BIN_FILE].contains(&name.as_str()))
+        .cloned()
+        .collect();
+
+    let remaining_bytes = files
+        .iter()
+        .map(|file| file.expected_size.saturating_sub(file.size.unwrap_or(0)))
+        .sum();
+    let resume_from_file = files
+        .iter()
+        .find(|file| file.status != FileStatus::Complete)
+        .map(|file| file.index);
+
+    let any_data = files.iter().any(|file| file.size.is_some()) || !extra_data_files.is_empty();
+    let state = if metadata_error.is_some()
+        || !extra_data_files.is_empty()
+        || files.iter().any(|file| file.status == FileStatus::Oversized)
+        || (any_data && !has_metadata)
+    {
+        DirectoryState::Inconsistent
+    } else if !any_data && !has_metadata {
+        DirectoryState::Empty
+    } else if resume_from_file.is_none() && has_nonce {
+        DirectoryState::Complete
+    } else {
+        DirectoryState::Partial
+    };
+
+    Ok(PosDirectoryReport {
+        state,
+        layout_source,
+        num_files,
+        files,
+        extra_data_files,
+        unexpected_files,
+        has_metadata,
+        has_nonce,
+        metadata_error,
+        has_identity_key: other_files.iter().any(|name| name == IDENTITY_KEY_FILE),
+        has_key_bin: other_files.iter().any(|name| name == KEY_BIN_FILE),
+        resume_from_file,
+        remaining_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::data_dir;
+    use std::path::PathBuf;
+
+    /// 4 units of 1024 labels in files of 1024 labels: 4 files of 16 KiB
+    const FILE_SIZE: usize = 16 * 1024;
+
+    fn config() -> PostCliConfig {
+        PostCliConfig {
+            num_units: 4,
+            labels_per_unit: Some(1024),
+            max_file_size: Some(FILE_SIZE as u64),
+            ..PostCliConfig::default()
+        }
+    }
+
+    /// Creates a directory with data files of the given sizes and optional metadata
+    fn pos_dir(name: &str, sizes: &[usize], nonce: Option<Option<u64>>) -> PathBuf {
+        let dir = data_dir(&format!("pos-inspect-{}", name), sizes);
+        if let Some(nonce) = nonce {
+            let nonce = nonce.map_or(String::new(), |n| format!(r#","Nonce":{}"#, n));
+            let metadata = format!(
+                r#"{{"NodeId":"","CommitmentAtxId":"","LabelsPerUnit":1024,"NumUnits":4,"MaxFileSize":{}{}}}"#,
+                FILE_SIZE, nonce
+            );
+            fs::write(dir.join(METADATA_FILE), metadata).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn classifies_empty_directory() {
+        let dir = pos_dir("empty", &[], None);
+        fs::write(dir.join(IDENTITY_KEY_FILE), b"key").unwrap();
+        let report = inspect(&dir, &config()).unwrap();
+        assert_eq!(report.state, DirectoryState::Empty);
+        assert_eq!(report.layout_source, LayoutSource::Config);
+        assert!(report.has_identity_key);
+        assert_eq!(report.resume_from_file, Some(0));
+        assert_eq!(report.remaining_bytes, 4 * FILE_SIZE as u64);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn classifies_partial_directory() {
+        let dir = pos_dir("partial", &[FILE_SIZE, FILE_SIZE / 2], Some(None));
+        let report = inspect(&dir, &PostCliConfig::default()).unwrap();
+        assert_eq!(report.state, DirectoryState::Partial);
+        assert_eq!(report.layout_source, LayoutSource::Metadata);
+        let statuses: Vec<FileStatus> = report.files.iter().map(|f| f.status).collect();
+        assert_eq!(
+            statuses,
+            vec![FileStatus::Complete, FileStatus::Truncated, FileStatus::Missing, FileStatus::Missing]
+        );
+        assert_eq!(report.resume_from_file, Some(1));
+        assert_eq!(report.remaining_bytes, (2 * FILE_SIZE + FILE_SIZE / 2) as u64);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn classifies_complete_directory() {
+        let dir = pos_dir("complete", &[FILE_SIZE; 4], Some(Some(7)));
+        fs::write(dir.join(".DS_Store"), b"").unwrap();
+        let report = inspect(&dir, &config()).unwrap();
+        assert_eq!(report.state, DirectoryState::Complete);
+        assert_eq!(report.unexpected_files, vec![".DS_Store".to_string()]);
+        assert_eq!(report.resume_from_file, None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn classifies_inconsistent_directory() {
+        let dir = pos_dir("extra", &[FILE_SIZE; 5], Some(Some(7)));
+        let report = inspect(&dir, &config()).unwrap();
+        assert_eq!(report.state, DirectoryState::Inconsistent);
+        assert_eq!(report.extra_data_files, vec!["postdata_4.bin".to_string()]);
+        fs::remove_dir_all(dir).unwrap();
+
+        let dir = pos_dir("oversized", &[FILE_SIZE + 1], Some(None));
+        let report = inspect(&dir, &config()).unwrap();
+        assert_eq!(report.state, DirectoryState::Inconsistent);
+        assert_eq!(report.files[0].status, FileStatus::Oversized);
+        fs::remove_dir_all(dir).unwrap();
+
+        let dir = pos_dir("no-metadata", &[FILE_SIZE], None);
+        assert_eq!(inspect(&dir, &config()).unwrap().state, DirectoryState::Inconsistent);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,255 @@
+//! PoS Metadata Module
+//!
+//! This module reads and checks the `postdata_metadata.json` file PostCLI writes
+//! next to the PoS data. The file stores the identity and the size parameters of
+//! the initialization, which the application uses to resume it with the same
+//! settings, and the nonce found once the initialization is finished.
+//!
+//! Besides decoding the file, the module reports inconsistencies between the
+//! metadata, the selected network and the data files present in the directory.
+
+use std::fs;
+use std::path::Path;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use super::pos_data::{list_data_files, PosLayout, DEFAULT_LABELS_PER_UNIT};
+
+/// Name of the metadata file in a PoS data directory
+pub const METADATA_FILE: &str = "postdata_metadata.json";
+
+/// Contents of `postdata_metadata.json` as written by PostCLI
+///
+/// Identifiers are stored as base64, the nonce value as hex.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PostMetadata {
+    pub node_id: String,
+    pub commitment_atx_id: String,
+    pub labels_per_unit: u64,
+    pub num_units: u32,
+    pub max_file_size: u64,
+    /// Index of the label with the lowest hash, present once the initialization is finished
+    #[serde(default)]
+    pub nonce: Option<u64>,
+    #[serde(default)]
+    pub nonce_value: Option<String>,
+}
+
+/// Inconsistency found between the metadata and its directory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetadataProblem {
+    /// Machine-readable kind of the problem:
+    /// `labels_per_unit_mismatch`, `missing_files`, `extra_files` or `init_not_finished`
+    pub kind: &'static str,
+    pub message: String,
+}
+
+/// Decoded metadata of a PoS data directory
+#[derive(Debug, Serialize)]
+pub struct PosMetadataReport {
+    /// Node identity (public key) as a lowercase hex string
+    pub node_id: String,
+    /// Commitment ATX ID as a lowercase hex string
+    pub commitment_atx_id: String,
+    pub labels_per_unit: u64,
+    pub num_units: u32,
+    pub max_file_size: u64,
+    pub nonce: Option<u64>,
+    pub nonce_value: Option<String>,
+    /// Number of data files of a complete initialization
+    pub expected_files: u32,
+    /// Number of `postdata_N.bin` files found in the directory
+    pub found_files: u32,
+    /// Empty if the directory is consistent
+    pub problems: Vec<MetadataProblem>,
+}
+
+impl PostMetadata {
+    /// Reads the metadata file of a PoS data directory
+    pub fn read(dir: &Path) -> Result<PostMetadata, String> {
+        let path = dir.join(METADATA_FILE);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    /// Layout of the data described by the metadata
+    pub fn layout(&self, dir: &Path) -> PosLayout {
+        PosLayout {
+            datadir: dir.to_path_buf(),
+            num_units: self.num_units,
+            labels_per_unit: self.labels_per_unit,
+            max_file_size: self.max_file_size,
+            from_file: None,
+            to_file: None,
+        }
+    }
+
+    /// Checks the metadata against the network and the data files in `dir`
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory holding the metadata
+    /// * `labels_per_unit` - Labels per unit of the selected network
+    pub fn check(&self, dir: &Path, labels_per_unit: u64) -> Vec<MetadataProblem> {
+        let mut problems = Vec::new();
+        let mut problem = |kind: &'static str, message: String| problems.push(MetadataProblem { kind, message });
+
+        if self.labels_per_unit != labels_per_unit {
+            problem(
+                "labels_per_unit_mismatch",
+                format!(
+                    "LabelsPerUnit mismatch, got {}, expected {} for the selected network",
+                    self.labels_per_unit, labels_per_unit
+                ),
+            );
+        }
+
+        let expected = self.layout(dir).num_files();
+        let found = count_data_files(dir);
+        let finished = self.nonce.is_some();
+        if found > expected {
+            problem(
+                "extra_files",
+                format!("Found {} data files, but the metadata describes only {}", found, expected),
+            );
+        } else if finished && found < expected {
+            problem(
+                "missing_files",
+                format!("Initialization is finished, but only {} of {} data files are present", found, expected),
+            );
+        }
+        if !finished {
+            problem(
+                "init_not_finished",
+                format!("No nonce found, the initialization is not finished ({} of {} files present)", found, expected),
+            );
+        }
+
+        problems
+    }
+}
+
+/// Reads and checks the metadata of a PoS data directory
+///
+/// # Arguments
+///
+/// * `dir` - PoS data directory
+/// * `labels_per_unit` - Labels per unit of the selected network, MainNet if omitted
+///
+/// # Returns
+///
+/// * `Ok(PosMetadataReport)` - Decoded metadata and the problems found
+/// * `Err(String)` - If the metadata file is missing or malformed
+///
+/// # Example
+///
+/// ```rust
+/// let report = read_pos_metadata("/path/to/post/data".to_string(), None)?;
+/// if report.problems.is_empty() {
+///     println!("Resuming initialization of {}", report.node_id);
+/// }
+/// ```
+#[tauri::command]
+pub fn read_pos_metadata(dir: String, labels_per_unit: Option<u64>) -> Result<PosMetadataReport, String> {
+    let dir = Path::new(&dir);
+    let metadata = PostMetadata::read(dir)?;
+    let problems = metadata.check(dir, labels_per_unit.unwrap_or(DEFAULT_LABELS_PER_UNIT));
+
+    Ok(PosMetadataReport {
+        node_id: base64_to_hex(&metadata.node_id).map_err(|e| format!("Invalid NodeId: {}", e))?,
+        commitment_atx_id: base64_to_hex(&metadata.commitment_atx_id)
+            .map_err(|e| format!("Invalid CommitmentAtxId: {}", e))?,
+        labels_per_unit: metadata.labels_per_unit,
+        num_units: metadata.num_units,
+        max_file_size: metadata.max_file_size,
+        nonce: metadata.nonce,
+        nonce_value: metadata.nonce_value.clone(),
+        expected_files: metadata.layout(dir).num_files(),
+        found_files: count_data_files(dir),
+        problems,
+    })
+}
+
+/// Decodes a base64 string to lowercase hex
+pub fn base64_to_hex(value: &str) -> Result<String, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(value)
+        .map_err(|e| e.to_string())?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Number of `postdata_N.bin` files in a directory
+fn count_data_files(dir: &Path) -> u32 {
+    list_data_files(dir).map(|files| files.len() as u32).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::data_dir;
+
+    fn metadata(nonce: Option<u64>) -> PostMetadata {
+        PostMetadata {
+            node_id: String::new(),
+            commitment_atx_id: String::new(),
+            labels_per_unit: 1024,
+            num_units: 4,
+            max_file_size: 16 * 1024,
+            nonce,
+            nonce_value: None,
+        }
+    }
+
+    fn kinds(problems: &[MetadataProblem]) -> Vec<&'static str> {
+        problems.iter().map(|p| p.kind).collect()
+    }
+
+    #[test]
+    fn decodes_base64_ids() {
+        assert_eq!(base64_to_hex("3q2+7w==").unwrap(), "deadbeef");
+        assert!(base64_to_hex("not base64!").is_err());
+    }
+
+    #[test]
+    fn parses_postcli_metadata() {
+        let metadata: PostMetadata = serde_json::from_str(
+            r#"{"NodeId":"3q2+7w==","CommitmentAtxId":"3q2+7w==","LabelsPerUnit":4294967296,
+                "NumUnits":4,"MaxFileSize":4294967296,"Nonce":42,"NonceValue":"00ff","LastPosition":null}"#,
+        )
+        .unwrap();
+        assert_eq!(metadata.num_units, 4);
+        assert_eq!(metadata.nonce, Some(42));
+        assert_eq!(metadata.nonce_value.as_deref(), Some("00ff"));
+    }
+
+    #[test]
+    fn accepts_finished_initialization() {
+        // 4 units of 1024 labels in files of 1024 labels make 4 files
+        let dir = data_dir("pos-metadata-finished", &[0; 4]);
+        assert!(metadata(Some(1)).check(&dir, 1024).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_unfinished_initialization_and_network_mismatch() {
+        let dir = data_dir("pos-metadata-unfinished", &[0; 2]);
+        assert_eq!(
+            kinds(&metadata(None).check(&dir, DEFAULT_LABELS_PER_UNIT)),
+            vec!["labels_per_unit_mismatch", "init_not_finished"]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_file_count_mismatch() {
+        let dir = data_dir("pos-metadata-missing", &[0; 3]);
+        assert_eq!(kinds(&metadata(Some(1)).check(&dir, 1024)), vec!["missing_files"]);
+        fs::remove_dir_all(dir).unwrap();
+
+        let dir = data_dir("pos-metadata-extra", &[0; 5]);
+        assert_eq!(kinds(&metadata(Some(1)).check(&dir, 1024)), vec!["extra_files"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
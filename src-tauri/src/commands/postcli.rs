@@ -6,44 +6,72 @@
 //! 
 //! The module supports both Unix-like systems and Windows, with platform-specific
 //! implementations for process management operations.
+//! 
+//! The frontend can only run the PostCLI operations the UI needs through dedicated
+//! commands (provider listing, version, help, initialization and proof generation).
+//! The generic runner is restricted to developer mode.
 
+use std::fs::File;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
-use std::thread;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 use serde::Serialize;
-use tauri::Manager;
-use std::io::{BufRead, BufReader};
+use tauri::{Manager, State};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+use super::calls::{self, CallRegistry, CommandOutput};
+use super::config::{PostCliConfig, PostCliError};
+use super::job_logs::{JobLog, JobLogs};
+use super::job_store::{JobRecord, JobStore, OutputFiles};
+use super::jobs::{self, Job, JobId, JobInfo, JobRegistry, JobStatus};
+use super::log_parser::{self, PostcliEvent};
+use super::monitor;
+use super::pos_data::PosLayout;
 
 // Platform-specific imports for process management
 #[cfg(unix)]
-use nix::sys::signal::{kill, Signal};
+use nix::sys::signal::{killpg, Signal};
 #[cfg(unix)]
 use nix::unistd::Pid;
-#[cfg(windows)]
-use winapi::um::processthreadsapi::OpenProcess;
-#[cfg(windows)]
-use winapi::um::processthreadsapi::TerminateProcess;
-#[cfg(windows)]
-use winapi::um::winnt::PROCESS_TERMINATE;
-#[cfg(windows)]
-use winapi::um::handleapi::CloseHandle;
 
-/// Represents the output of a synchronous PostCLI command execution
-#[derive(Serialize)]
-pub struct CommandOutput {
-    stdout: String,
-    stderr: String,
-}
+/// Interval between checks of a job's output files for new lines
+const OUTPUT_POLL_INTERVAL: Duration = Duration::from_millis(200);
 
 /// Contains information about a detached PostCLI process
 #[derive(Serialize)]
 pub struct DetachedProcessInfo {
+    /// Registry id of the job owning the process
+    pub job_id: JobId,
     /// Process ID of the detached process
     process_id: u32,
     /// Human-readable status message
     message: String,
 }
 
+impl DetachedProcessInfo {
+    fn new(job: &Job) -> DetachedProcessInfo {
+        DetachedProcessInfo {
+            job_id: job.id,
+            process_id: job.pid,
+            message: format!(
+                "POS data generation started in background as job {} with process ID: {}",
+                job.id, job.pid
+            ),
+        }
+    }
+}
+
+/// Payload of the 'postcli-progress' event
+#[derive(Clone, Serialize)]
+pub struct ProgressEvent {
+    /// Registry id of the job that produced the log line
+    job_id: JobId,
+    /// Typed event parsed from the log line
+    event: PostcliEvent,
+}
+
 /// Determines the platform-specific path to the PostCLI executable
 /// 
 /// This function constructs the path to the PostCLI executable based on the current
@@ -74,32 +102,136 @@ fn get_postcli_path(
     path
 }
 
-/// Executes a PostCLI command synchronously
+/// Environment variable enabling developer mode in release builds
+const DEVELOPER_MODE_ENV: &str = "SM_INIT_DEVELOPER_MODE";
+
+/// Whether the generic PostCLI runner is exposed to the frontend
+///
+/// Developer mode is always on in debug builds. Release builds only enable it
+/// when the `SM_INIT_DEVELOPER_MODE` environment variable is set to `1` or `true`
+/// at startup; it cannot be switched on from the webview.
+pub struct DeveloperMode {
+    enabled: bool,
+}
+
+impl DeveloperMode {
+    /// Determines the developer mode from the build profile and environment
+    pub fn from_env() -> DeveloperMode {
+        let from_env = std::env::var(DEVELOPER_MODE_ENV)
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        DeveloperMode {
+            enabled: cfg!(debug_assertions) || from_env,
+        }
+    }
+}
+
+/// Returns whether developer mode is enabled
+#[tauri::command]
+pub fn is_developer_mode(developer_mode: State<'_, DeveloperMode>) -> bool {
+    developer_mode.enabled
+}
+
+/// Executes a PostCLI command and waits for it to exit (developer mode only)
 /// 
-/// This command runs the PostCLI executable with the provided arguments and waits
-/// for completion, capturing both stdout and stderr.
+/// This command validates the configuration, runs the PostCLI executable with the
+/// rendered arguments and waits for completion, capturing both stdout and stderr.
+/// It is the generic runner for experimenting with PostCLI flags; the UI uses the
+/// dedicated commands for providers, version, help, initialization and proofs.
 /// 
 /// # Arguments
 /// 
-/// * `args` - Vector of command-line arguments to pass to PostCLI
+/// * `config` - Typed PostCLI configuration
+/// * `call_id` - Optional id to cancel the call with `cancel_postcli_call`
+/// * `timeout_secs` - Time after which PostCLI is killed (default 60 seconds)
+/// * `developer_mode` - Developer mode gating this command
+/// * `calls` - Registry of running calls
 /// 
 /// # Returns
 /// 
-/// * `Ok(CommandOutput)` - Contains stdout and stderr if execution was successful
-/// * `Err(String)` - Error message if execution failed
+/// * `Ok(CommandOutput)` - Contains stdout, stderr and the exit status
+/// * `Err(PostCliError)` - Validation errors, or an error message if execution failed,
+///   timed out or was cancelled, or developer mode is disabled
 /// 
 /// # Example
 /// 
 /// ```rust
-/// let output = run_postcli_command(vec!["--version".to_string()])?;
-/// println!("PostCLI output: {}", output.stdout);
+/// let config = PostCliConfig { print_providers: true, ..PostCliConfig::default() };
+/// let output = run_postcli_command(app_handle, config, None, Some(10), developer_mode, calls).await?;
+/// println!("PostCLI exited with {:?}: {}", output.exit_code, output.stdout);
 /// ```
 #[tauri::command]
-pub fn run_postcli_command(
+pub async fn run_postcli_command(
     app: tauri::AppHandle,
-    args: Vec<String>
+    config: PostCliConfig,
+    call_id: Option<String>,
+    timeout_secs: Option<u64>,
+    developer_mode: State<'_, DeveloperMode>,
+    calls: State<'_, CallRegistry>,
+) -> Result<CommandOutput, PostCliError> {
+    if !developer_mode.enabled {
+        return Err(format!(
+            "The generic postcli runner is only available in developer mode (set {}=1)",
+            DEVELOPER_MODE_ENV
+        )
+        .into());
+    }
+    config.validate()?;
+    Ok(run_postcli_call(app, config.to_args(), call_id, timeout_secs, &calls).await?)
+}
+
+/// Lists the OpenCL providers available to PostCLI (`-printProviders`)
+/// 
+/// # Arguments
+/// 
+/// * `call_id` - Optional id to cancel the call with `cancel_postcli_call`
+/// * `timeout_secs` - Time after which PostCLI is killed (default 60 seconds)
+/// 
+/// # Returns
+/// 
+/// * `Ok(CommandOutput)` - PostCLI's provider listing on stdout and its exit status
+/// * `Err(String)` - Error message if execution failed, timed out or was cancelled
+#[tauri::command]
+pub async fn print_postcli_providers(
+    app: tauri::AppHandle,
+    call_id: Option<String>,
+    timeout_secs: Option<u64>,
+    calls: State<'_, CallRegistry>,
 ) -> Result<CommandOutput, String> {
-    let path = get_postcli_path(app.clone())?;
+    run_postcli_call(app, vec!["-printProviders".to_string()], call_id, timeout_secs, &calls).await
+}
+
+/// Returns the version of the bundled PostCLI (`-version`)
+#[tauri::command]
+pub async fn get_postcli_version(
+    app: tauri::AppHandle,
+    call_id: Option<String>,
+    timeout_secs: Option<u64>,
+    calls: State<'_, CallRegistry>,
+) -> Result<CommandOutput, String> {
+    run_postcli_call(app, vec!["-version".to_string()], call_id, timeout_secs, &calls).await
+}
+
+/// Returns the usage information of the bundled PostCLI (`-help`)
+#[tauri::command]
+pub async fn get_postcli_help(
+    app: tauri::AppHandle,
+    call_id: Option<String>,
+    timeout_secs: Option<u64>,
+    calls: State<'_, CallRegistry>,
+) -> Result<CommandOutput, String> {
+    run_postcli_call(app, vec!["-help".to_string()], call_id, timeout_secs, &calls).await
+}
+
+/// Runs PostCLI with the given arguments until it exits, times out or is cancelled
+async fn run_postcli_call(
+    app: tauri::AppHandle,
+    args: Vec<String>,
+    call_id: Option<String>,
+    timeout_secs: Option<u64>,
+    calls: &CallRegistry,
+) -> Result<CommandOutput, String> {
+    let path = get_postcli_path(app)?;
 
     // Check if postcli exists
     if !path.exists() {
@@ -109,56 +241,102 @@ pub fn run_postcli_command(
         ));
     }
 
-    let postcli_path = path.to_str().ok_or("Invalid path")?;
-
     println!("Executing postcli with args: {:?}", args);
 
-    let output = Command::new(postcli_path)
-        .args(&args)
-        .output()
-        .map_err(|e| format!("Failed to execute postcli: {}", e))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-    // Log the output for debugging
-    if !stdout.is_empty() {
-        println!("postcli stdout: {}", stdout);
-    }
-    if !stderr.is_empty() {
-        eprintln!("postcli stderr: {}", stderr);
-    }
-
-    Ok(CommandOutput { stdout, stderr })
+    let mut command = Command::new(path);
+    command.args(&args);
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(calls::DEFAULT_CALL_TIMEOUT_SECS));
+    calls::run_call(command, call_id, timeout, calls).await
 }
 
 /// Executes a PostCLI command asynchronously in a detached process
 /// 
-/// This command starts PostCLI in a separate process and sets up output streaming
-/// through Tauri events. The process runs independently of the main application.
+/// This command starts PostCLI in a separate process, hands the process over to the
+/// job registry and sets up output streaming through Tauri events.
 /// 
 /// # Arguments
 /// 
-/// * `args` - Vector of command-line arguments to pass to PostCLI
+/// * `config` - Typed PostCLI configuration of the initialization
 /// * `app` - Tauri application handle for event emission
+/// * `registry` - Job registry taking ownership of the spawned process
+/// * `store` - Job store persisting a record of the job
 /// 
 /// # Returns
 /// 
-/// * `Ok(DetachedProcessInfo)` - Contains process ID and status message
-/// * `Err(String)` - Error message if process creation failed
+/// * `Ok(DetachedProcessInfo)` - Contains job ID, process ID and status message
+/// * `Err(PostCliError)` - Validation errors, or an error message if process creation failed
 /// 
 /// # Events
 /// 
-/// Emits 'postcli-log' events with stdout/stderr content as they become available
+/// * 'postcli-log' - stdout/stderr content as it becomes available
+/// * 'postcli-progress' - typed progress events parsed from the output
+/// * 'postcli-disk-progress' - bytes written, rates and ETA measured on the data directory
+/// * 'postcli-exit' - exit code/signal, runtime and last stderr lines once the process exits
 /// 
 /// # Example
 /// 
 /// ```rust
-/// let process_info = run_postcli_detached(vec!["--generate".to_string()], app_handle).await?;
-/// println!("Started process: {}", process_info.process_id);
+/// let process_info = run_postcli_detached(config, app_handle, registry, store).await?;
+/// println!("Started job {} (pid {})", process_info.job_id, process_info.process_id);
 /// ```
 #[tauri::command]
-pub async fn run_postcli_detached(args: Vec<String>, app: tauri::AppHandle) -> Result<DetachedProcessInfo, String> {
+pub async fn run_postcli_detached(
+    config: PostCliConfig,
+    app: tauri::AppHandle,
+    registry: State<'_, JobRegistry>,
+    store: State<'_, JobStore>,
+) -> Result<DetachedProcessInfo, PostCliError> {
+    config.validate_init()?;
+    let job = spawn_detached(app, registry.inner(), store.inner(), config.to_args())?;
+    Ok(DetachedProcessInfo::new(&job))
+}
+
+/// Generates a proof over an initialized data directory in a detached process
+/// 
+/// PostCLI skips the files that are already complete, finishes the initialization
+/// if needed and then generates a proof with `-genproof` to verify the data.
+/// 
+/// # Arguments
+/// 
+/// * `config` - Typed PostCLI configuration of the initialization to verify
+/// * `app` - Tauri application handle for event emission
+/// * `registry` - Job registry taking ownership of the spawned process
+/// * `store` - Job store persisting a record of the job
+/// 
+/// # Returns
+/// 
+/// * `Ok(DetachedProcessInfo)` - Contains job ID, process ID and status message
+/// * `Err(PostCliError)` - Validation errors, or an error message if process creation failed
+#[tauri::command]
+pub async fn run_postcli_genproof(
+    config: PostCliConfig,
+    app: tauri::AppHandle,
+    registry: State<'_, JobRegistry>,
+    store: State<'_, JobStore>,
+) -> Result<DetachedProcessInfo, PostCliError> {
+    let config = PostCliConfig {
+        genproof: true,
+        ..config
+    };
+    config.validate_init()?;
+    let job = spawn_detached(app, registry.inner(), store.inner(), config.to_args())?;
+    Ok(DetachedProcessInfo::new(&job))
+}
+
+/// Starts PostCLI in a detached process owned by the job registry
+/// 
+/// PostCLI writes its output to files rather than pipes, so that it does not
+/// depend on the application to keep reading them and survives its exit.
+/// Besides streaming the output from these files, this persists a record of the
+/// job and starts monitoring its data directory and waiting for its exit in the background.
+///
+/// The returned job stays usable after it exited, even once the registry pruned it.
+pub fn spawn_detached(
+    app: tauri::AppHandle,
+    registry: &JobRegistry,
+    store: &JobStore,
+    args: Vec<String>,
+) -> Result<Arc<Job>, String> {
     let path = get_postcli_path(app.clone())?;
 
     // Check if postcli exists
@@ -173,104 +351,401 @@ pub async fn run_postcli_detached(args: Vec<String>, app: tauri::AppHandle) -> R
 
     println!("Executing postcli in detached mode with args: {:?}", args);
 
-    let child = Command::new(postcli_path)
+    let (output, stdout, stderr) = store.create_output_files()?;
+    let mut command = Command::new(postcli_path);
+    command
         .args(&args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to execute postcli: {}", e))?;
-
-    let process_id = child.id();
-
-    // Handle stdout in a separate thread
-    if let Some(stdout) = child.stdout {
-        let app_clone = app.clone();
-        thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    println!("postcli stdout: {}", line);
-                    app_clone.emit_all("postcli-log", format!("stdout: {}", line)).unwrap();
-                }
+        .stdin(Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr);
+
+    // Run postcli in its own process group, so that stopping it also stops its helpers
+    #[cfg(unix)]
+    unsafe {
+        use std::os::unix::process::CommandExt;
+        command.pre_exec(|| {
+            if libc::setpgid(0, 0) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
             }
         });
     }
 
-    // Handle stderr in a separate thread
-    if let Some(stderr) = child.stderr {
-        let app_clone = app.clone();
-        thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    eprintln!("postcli stderr: {}", line);
-                    app_clone.emit_all("postcli-log", format!("stderr: {}", line)).unwrap();
+    let child = command.spawn().map_err(|e| {
+        output.remove();
+        format!("Failed to execute postcli: {}", e)
+    })?;
+
+    let layout = PosLayout::from_args(&args);
+    let job = registry.register(child, args);
+    store.insert(JobRecord::new(&job, &layout.datadir.to_string_lossy(), output.clone()));
+    let log = app.try_state::<JobLogs>().and_then(|logs| logs.create(&job));
+    let readers = spawn_output_readers(app.clone(), job.clone(), log, &output, false);
+
+    monitor::start_disk_monitor(app.clone(), job.clone(), layout, monitor::MONITOR_INTERVAL);
+    jobs::watch_job(app, job.clone(), readers);
+    Ok(job)
+}
+
+/// Streams the output files of a detached PostCLI job to the frontend
+/// 
+/// Starts one reader thread for stdout and one for stderr, see [`spawn_output_reader`].
+/// 
+/// # Arguments
+/// 
+/// * `app` - Tauri application handle for event emission
+/// * `job` - Job writing the output
+/// * `log` - Log file of the job, if job logs are available
+/// * `output` - Files the job writes its stdout and stderr to
+/// * `from_end` - Whether to skip the output written so far, e.g. when re-attaching to a job
+pub fn spawn_output_readers(
+    app: tauri::AppHandle,
+    job: Arc<Job>,
+    log: Option<Arc<JobLog>>,
+    output: &OutputFiles,
+    from_end: bool,
+) -> Vec<JoinHandle<()>> {
+    vec![
+        spawn_output_reader(app.clone(), job.clone(), log.clone(), "stdout", output.stdout.clone(), from_end),
+        spawn_output_reader(app, job, log, "stderr", output.stderr.clone(), from_end),
+    ]
+}
+
+/// Follows one output file of a detached PostCLI job
+/// 
+/// New lines are picked up by polling the file. Once the job has exited, the
+/// file is read to its end one last time and the thread stops.
+fn spawn_output_reader(
+    app: tauri::AppHandle,
+    job: Arc<Job>,
+    log: Option<Arc<JobLog>>,
+    stream: &'static str,
+    path: PathBuf,
+    from_end: bool,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("Failed to open postcli {} output {:?}: {}", stream, path, e);
+                return;
+            }
+        };
+        if from_end {
+            if let Err(e) = file.seek(SeekFrom::End(0)) {
+                eprintln!("Failed to seek postcli {} output {:?}: {}", stream, path, e);
+            }
+        }
+
+        let mut reader = BufReader::new(file);
+        let mut line = Vec::new();
+        let mut exited = false;
+        loop {
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) if exited => break,
+                Ok(0) => {
+                    // Checked before the final read, so output written right before the exit is not lost
+                    exited = !job.is_alive();
+                    if !exited {
+                        thread::sleep(OUTPUT_POLL_INTERVAL);
+                    }
+                }
+                Ok(_) if line.ends_with(b"\n") => {
+                    handle_output_line(&app, &job, log.as_deref(), stream, &line);
+                    line.clear();
+                }
+                // The rest of the line has not been written yet
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("Failed to read postcli {} output {:?}: {}", stream, path, e);
+                    break;
                 }
             }
-        });
+        }
+        if !line.is_empty() {
+            handle_output_line(&app, &job, log.as_deref(), stream, &line);
+        }
+    })
+}
+
+/// Reports one line of PostCLI output
+/// 
+/// Every line is logged, appended to the job's log file and emitted as a raw
+/// 'postcli-log' event. Lines carrying
+/// progress information are additionally emitted as typed 'postcli-progress' events.
+/// Lines written to stderr are kept in the job's stderr tail for the exit report.
+fn handle_output_line(app: &tauri::AppHandle, job: &Job, log: Option<&JobLog>, stream: &str, line: &[u8]) {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim_end_matches(['\r', '\n']);
+    if stream == "stderr" {
+        eprintln!("postcli stderr: {}", line);
+        job.push_stderr(line);
+    } else {
+        println!("postcli stdout: {}", line);
+    }
+    if let Some(log) = log {
+        log.write_line(stream, line);
     }
+    let _ = app.emit_all("postcli-log", format!("{}: {}", stream, line));
 
-    Ok(DetachedProcessInfo {
-        process_id,
-        message: format!("POS data generation started in background with process ID: {}", process_id),
-    })
+    if let Some(event) = log_parser::parse_line(line) {
+        let _ = app.emit_all("postcli-progress", ProgressEvent { job_id: job.id, event });
+    }
+}
+
+/// Lists the PostCLI jobs started during this session
+/// 
+/// # Returns
+/// 
+/// * `Vec<JobInfo>` - Job id, process id, arguments, start time, runtime and status of every job
+#[tauri::command]
+pub fn list_postcli_jobs(registry: State<'_, JobRegistry>) -> Vec<JobInfo> {
+    registry.list()
+}
+
+/// Lists PostCLI jobs that were interrupted by the application closing or crashing
+/// 
+/// # Returns
+/// 
+/// * `Vec<JobRecord>` - Arguments, data directory and last progress of every interrupted job
+#[tauri::command]
+pub fn list_interrupted_jobs(store: State<'_, JobStore>) -> Vec<JobRecord> {
+    store.filter(|r| r.interrupted)
+}
+
+/// Resumes an interrupted PostCLI job with its original arguments
+/// 
+/// PostCLI continues from the files already present in the data directory,
+/// so restarting it with the same arguments resumes the initialization.
+/// 
+/// # Arguments
+/// 
+/// * `record_id` - Id of the interrupted job record
+/// 
+/// # Returns
+/// 
+/// * `Ok(DetachedProcessInfo)` - Job ID, process ID and status message of the new job
+/// * `Err(String)` - Error message if the record is unknown or PostCLI could not be started
+#[tauri::command]
+pub async fn resume_interrupted_job(
+    record_id: String,
+    app: tauri::AppHandle,
+    registry: State<'_, JobRegistry>,
+    store: State<'_, JobStore>,
+) -> Result<DetachedProcessInfo, String> {
+    let record = store
+        .get(&record_id)
+        .filter(|r| r.interrupted)
+        .ok_or_else(|| format!("No interrupted job {}", record_id))?;
+
+    let job = spawn_detached(app, registry.inner(), store.inner(), record.args)?;
+    store.remove(&record_id);
+    Ok(DetachedProcessInfo::new(&job))
 }
 
-/// Terminates a running PostCLI process
+/// Forgets an interrupted PostCLI job
 /// 
-/// This command attempts to gracefully terminate a PostCLI process using
-/// platform-specific mechanisms (SIGTERM on Unix, TerminateProcess on Windows).
+/// # Arguments
+/// 
+/// * `record_id` - Id of the interrupted job record
+#[tauri::command]
+pub fn discard_interrupted_job(record_id: String, store: State<'_, JobStore>) {
+    store.remove(&record_id);
+}
+
+/// Default time PostCLI is given to exit after SIGTERM before it is killed
+pub const DEFAULT_STOP_GRACE_PERIOD_SECS: u64 = 10;
+
+/// Time to wait for a killed process to be reaped
+const KILL_REAP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of stopping a PostCLI job
+#[derive(Serialize)]
+pub struct StopResult {
+    job_id: JobId,
+    process_id: u32,
+    /// Exit code, if PostCLI exited normally
+    code: Option<i32>,
+    /// Terminating signal, if PostCLI was killed by one (Unix only)
+    signal: Option<i32>,
+    /// Whether PostCLI had to be killed after the grace period
+    escalated: bool,
+    /// Index of the last fully written `postdata_N.bin`, if any
+    last_completed_file: Option<u32>,
+}
+
+/// Stops a running PostCLI job and confirms its termination
+/// 
+/// This command asks PostCLI to shut down gracefully, waits for it to exit and
+/// escalates to a forced kill if it is still running after the grace period.
+/// The whole process group is signalled, so helper processes do not survive.
+/// It only returns once the process has been reaped.
 /// 
 /// # Arguments
 /// 
-/// * `pid` - Process ID of the PostCLI process to terminate
+/// * `job_id` - Registry id of the job to stop
+/// * `grace_period_secs` - Time to wait for a graceful exit (default 10 seconds)
+/// * `registry` - Job registry owning the process
 /// 
 /// # Returns
 /// 
-/// * `Ok(String)` - Success message if process was terminated
-/// * `Err(String)` - Error message if termination failed
+/// * `Ok(StopResult)` - Final exit status and last fully completed data file
+/// * `Err(String)` - Error message if the job is unknown, already exited or could not be stopped
 /// 
 /// # Platform-specific behavior
 /// 
-/// * Unix: Uses SIGTERM signal
-/// * Windows: Uses TerminateProcess API
+/// * Unix: Sends SIGTERM to the process group, then SIGKILL after the grace period
+/// * Windows: Uses TerminateProcess API right away
 /// 
 /// # Example
 /// 
 /// ```rust
-/// match stop_postcli_process(process_id) {
-///     Ok(msg) => println!("Process stopped: {}", msg),
-///     Err(e) => eprintln!("Failed to stop process: {}", e),
-/// }
+/// let result = stop_postcli_process(job_id, Some(30), registry).await?;
+/// println!("Stopped after file {:?}", result.last_completed_file);
 /// ```
 #[tauri::command]
-pub fn stop_postcli_process(pid: u32) -> Result<String, String> {
-    println!("Attempting to stop postcli process with PID: {}", pid);
+pub async fn stop_postcli_process(
+    job_id: JobId,
+    grace_period_secs: Option<u64>,
+    registry: State<'_, JobRegistry>,
+) -> Result<StopResult, String> {
+    let job = registry.get(job_id)?;
+    if !job.is_alive() {
+        return Err(format!("Job {} has already exited", job_id));
+    }
+    let grace_period = Duration::from_secs(grace_period_secs.unwrap_or(DEFAULT_STOP_GRACE_PERIOD_SECS));
+
+    println!("Attempting to stop postcli job {} with PID: {}", job_id, job.pid);
+
+    tauri::async_runtime::spawn_blocking(move || stop_job(&job, grace_period))
+        .await
+        .map_err(|e| format!("Failed to stop job {}: {}", job_id, e))?
+}
+
+/// Terminates a job's process, escalating to a forced kill after the grace period
+pub fn stop_job(job: &Job, grace_period: Duration) -> Result<StopResult, String> {
+    let pid = job.pid;
 
     #[cfg(unix)]
-    {
-        match kill(Pid::from_raw(pid as i32), Signal::SIGTERM) {
-            Ok(_) => Ok(format!("Successfully terminated process {}", pid)),
-            Err(e) => Err(format!("Failed to terminate process {}: {}", pid, e))
+    let (status, escalated) = {
+        signal_process_group(pid, Signal::SIGTERM)?;
+        // A stopped process only handles SIGTERM once it is continued
+        if matches!(job.status(), JobStatus::Paused) {
+            signal_process_group(pid, Signal::SIGCONT)?;
         }
-    }
 
-    #[cfg(windows)]
-    {
-        unsafe {
-            let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
-            if handle.is_null() {
-                return Err(format!("Failed to open process {}", pid));
+        match job.wait_for_exit(grace_period) {
+            Some(status) => (Some(status), false),
+            None => {
+                println!("postcli job {} did not exit within {:?}, killing it", job.id, grace_period);
+                signal_process_group(pid, Signal::SIGKILL)?;
+                (job.wait_for_exit(KILL_REAP_TIMEOUT), true)
             }
+        }
+    };
 
-            let result = TerminateProcess(handle, 0);
-            CloseHandle(handle);
+    #[cfg(windows)]
+    let (status, escalated) = {
+        let _ = grace_period;
+        job.terminate()?;
+        (job.wait_for_exit(KILL_REAP_TIMEOUT), false)
+    };
 
-            if result == 0 {
-                Err(format!("Failed to terminate process {}", pid))
-            } else {
-                Ok(format!("Successfully terminated process {}", pid))
-            }
-        }
-    }
+    let (code, signal) = match status {
+        Some(JobStatus::Exited { code, signal }) => (code, signal),
+        _ => return Err(format!("Process {} did not exit after being killed", pid)),
+    };
+
+    Ok(StopResult {
+        job_id: job.id,
+        process_id: pid,
+        code,
+        signal,
+        escalated,
+        last_completed_file: PosLayout::from_args(&job.args).last_complete_file(),
+    })
+}
+
+/// Sends a signal to the process group led by a PostCLI process
+#[cfg(unix)]
+fn signal_process_group(pid: u32, signal: Signal) -> Result<(), String> {
+    killpg(Pid::from_raw(pid as i32), signal)
+        .map_err(|e| format!("Failed to send {} to process {}: {}", signal, pid, e))
+}
+
+/// Pauses a running PostCLI job
+/// 
+/// Suspends the job's process without losing its position, so the machine can be
+/// freed for a while. The paused state is tracked in the job registry and announced
+/// through a 'postcli-state' event.
+/// 
+/// # Arguments
+/// 
+/// * `job_id` - Registry id of the job to pause
+/// * `app` - Tauri application handle for event emission
+/// * `registry` - Job registry owning the process
+/// 
+/// # Returns
+/// 
+/// * `Ok(String)` - Success message if the process was suspended
+/// * `Err(String)` - Error message if the job is unknown, not running or suspension failed
+/// 
+/// # Platform-specific behavior
+/// 
+/// * Unix: Sends SIGSTOP to the process group
+/// * Windows: Not supported
+#[tauri::command]
+pub fn pause_postcli_job(
+    job_id: JobId,
+    app: tauri::AppHandle,
+    registry: State<'_, JobRegistry>,
+) -> Result<String, String> {
+    let job = registry.get(job_id)?;
+    println!("Pausing postcli job {} with PID: {}", job_id, job.pid);
+
+    job.set_paused(&app, true, |pid| suspend_process(pid, true))?;
+    Ok(format!("Paused job {}", job_id))
+}
+
+/// Resumes a paused PostCLI job
+/// 
+/// # Arguments
+/// 
+/// * `job_id` - Registry id of the job to resume
+/// * `app` - Tauri application handle for event emission
+/// * `registry` - Job registry owning the process
+/// 
+/// # Returns
+/// 
+/// * `Ok(String)` - Success message if the process was continued
+/// * `Err(String)` - Error message if the job is unknown, not paused or continuation failed
+/// 
+/// # Platform-specific behavior
+/// 
+/// * Unix: Sends SIGCONT to the process group
+/// * Windows: Not supported
+#[tauri::command]
+pub fn resume_postcli_job(
+    job_id: JobId,
+    app: tauri::AppHandle,
+    registry: State<'_, JobRegistry>,
+) -> Result<String, String> {
+    let job = registry.get(job_id)?;
+    println!("Resuming postcli job {} with PID: {}", job_id, job.pid);
+
+    job.set_paused(&app, false, |pid| suspend_process(pid, false))?;
+    Ok(format!("Resumed job {}", job_id))
+}
+
+/// Suspends (`suspend == true`) or continues a process and its helpers
+#[cfg(unix)]
+fn suspend_process(pid: u32, suspend: bool) -> Result<(), String> {
+    signal_process_group(pid, if suspend { Signal::SIGSTOP } else { Signal::SIGCONT })
+}
+
+/// Suspends (`suspend == true`) or continues a process
+#[cfg(windows)]
+fn suspend_process(pid: u32, _suspend: bool) -> Result<(), String> {
+    Err(format!("Pausing process {} is not supported on Windows", pid))
 }
@@ -0,0 +1,120 @@
+//! Process Utilities Module
+//!
+//! This module provides platform-specific helpers for inspecting and terminating
+//! processes by PID. They are used for PostCLI processes the application did not
+//! spawn in the current session, e.g. ones left running by a previous session.
+
+#[cfg(not(target_os = "linux"))]
+use std::process::Command;
+
+#[cfg(unix)]
+use nix::errno::Errno;
+#[cfg(unix)]
+use nix::sys::signal::kill;
+#[cfg(unix)]
+use nix::unistd::Pid;
+#[cfg(windows)]
+use winapi::um::handleapi::CloseHandle;
+#[cfg(windows)]
+use winapi::um::minwinbase::STILL_ACTIVE;
+#[cfg(windows)]
+use winapi::um::processthreadsapi::{GetExitCodeProcess, OpenProcess, TerminateProcess};
+#[cfg(windows)]
+use winapi::um::winnt::{PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE};
+
+/// Checks whether a process with the given PID is alive
+#[cfg(unix)]
+pub fn process_alive(pid: u32) -> bool {
+    // Signal 0 only performs the existence and permission checks
+    match kill(Pid::from_raw(pid as i32), None) {
+        Ok(_) | Err(Errno::EPERM) => true,
+        Err(_) => false,
+    }
+}
+
+/// Checks whether a process with the given PID is alive
+#[cfg(windows)]
+pub fn process_alive(pid: u32) -> bool {
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return false;
+        }
+        let mut exit_code = 0;
+        let result = GetExitCodeProcess(handle, &mut exit_code);
+        CloseHandle(handle);
+        result != 0 && exit_code == STILL_ACTIVE
+    }
+}
+
+/// Checks whether the process with the given PID is a PostCLI executable
+///
+/// This guards against PID reuse when re-attaching to a process recorded in a
+/// previous session.
+pub fn is_postcli_process(pid: u32) -> bool {
+    process_alive(pid)
+        && process_name(pid)
+            .map(|name| name.trim().trim_end_matches(".exe").ends_with("postcli"))
+            .unwrap_or(false)
+}
+
+/// Returns the executable name of a process
+#[cfg(target_os = "linux")]
+fn process_name(pid: u32) -> Option<String> {
+    let cmdline = std::fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    let program = cmdline.split(|b| *b == 0).next()?;
+    let program = std::path::Path::new(std::str::from_utf8(program).ok()?);
+    program.file_name()?.to_str().map(|s| s.to_string())
+}
+
+/// Returns the executable name of a process
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_name(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-p", pid.to_string().as_str(), "-o", "comm="])
+        .output()
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// Returns the executable name of a process
+#[cfg(windows)]
+fn process_name(pid: u32) -> Option<String> {
+    let output = Command::new("tasklist")
+        .args(["/FI", format!("PID eq {}", pid).as_str(), "/FO", "CSV", "/NH"])
+        .output()
+        .ok()?;
+    // Output format: "postcli.exe","1234","Console","1","12,345 K"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let name = stdout.split(',').next()?.trim().trim_matches('"');
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Terminates a process by PID using the TerminateProcess API
+#[cfg(windows)]
+pub fn terminate_process(pid: u32) -> Result<(), String> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+        if handle.is_null() {
+            return Err(format!("Failed to open process {}", pid));
+        }
+
+        let result = TerminateProcess(handle, 0);
+        CloseHandle(handle);
+
+        if result == 0 {
+            Err(format!("Failed to terminate process {}", pid))
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -10,15 +10,59 @@
 //! - Data size
 //! - Test duration
 //! - Custom data file paths
+//!
+//! A sweep runs the profiler over ranges of nonces and threads against the same
+//! data file and reports the fastest thread count for every nonce count.
+//!
+//! Runs and sweeps execute in a blocking task and can be cancelled by the run id
+//! they were started with. Temporary data files are removed when a run fails or
+//! is cancelled, as a partially generated file cannot be reused. Every
+//! successful run is recorded in the profiler history.
+//!
+//! The profiler generates its data file if it is missing and extends it if it is
+//! smaller than the data size, so a custom data file must never be a file of a
+//! PoS data directory. Existing PoS data is profiled in read-only mode instead.
+//! The profiler binary has no read-only flag, so this mode is not enforced: it
+//! only refuses files smaller than the data size, which the profiler would
+//! extend, and fails the run afterwards if the size or modification time of the
+//! file changed while it ran.
+//!
+//! # Events
+//!
+//! Emits 'profiler-progress' events with a [`ProfilerProgress`] payload while a
+//! run is active, with the elapsed time and every line the profiler prints, and a
+//! 'profiler-sweep-progress' event with a [`SweepProgress`] payload after every
+//! point of a sweep.
 
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 use serde::{Deserialize, Serialize};
-use tauri::command;
+use tauri::{command, Manager, State};
+
+use super::pos_data::parse_data_file_name;
+use super::pos_inspect::{inspect_pos_directory, DirectoryState, FileStatus, IDENTITY_KEY_FILE, KEY_BIN_FILE};
+use super::pos_metadata::METADATA_FILE;
+use super::profiler_history::ProfilerHistory;
+use super::proving::PostParams;
+
+/// Interval between two checks of a running profiler for exit and cancellation
+const RUN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Interval between two elapsed-time progress events
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Represents the results of a profiling run
 /// 
 /// Contains comprehensive metrics and configuration details from
 /// a completed profiling operation.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProfilerResult {
     pub nonces: u32,
     pub threads: u32,
@@ -27,9 +71,12 @@ pub struct ProfilerResult {
     pub data_size: u32, // in GiB
     pub duration: u32,  // in seconds
     pub data_file: Option<String>, // Path to data file used
+    /// Whether the data file was only read, see [`ProfilerConfig::read_only`]
+    #[serde(default)]
+    pub read_only: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProfilerConfig {
     /// Size of data to process in GiB
     pub data_size: u32,
@@ -37,6 +84,64 @@ pub struct ProfilerConfig {
     pub duration: u32,
     /// Optional custom path for the data file
     pub data_file: Option<String>,
+    /// Only read an existing data file, never create, extend or remove it
+    ///
+    /// Not enforced by the profiler binary, a run is refused if the file is
+    /// smaller than the data size and fails if the file changed while it ran.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl Default for ProfilerConfig {
+    fn default() -> Self {
+        ProfilerConfig {
+            data_size: 1, // Default 1 GiB
+            duration: 10, // Default 10 seconds
+            data_file: None, // No custom path by default
+            read_only: false,
+        }
+    }
+}
+
+/// Payload of the 'profiler-progress' event
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfilerProgress {
+    /// Id the run was started with, or the one generated for it
+    pub run_id: String,
+    pub nonces: u32,
+    pub threads: u32,
+    /// Time since the profiler was started in seconds
+    pub elapsed_s: f64,
+    /// Line printed by the profiler, `None` for periodic elapsed-time updates
+    pub line: Option<String>,
+}
+
+/// Tauri-managed registry of the cancellation flags of running profiler runs
+#[derive(Default)]
+pub struct ProfilerRuns {
+    next_id: AtomicU64,
+    runs: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl ProfilerRuns {
+    /// Registers a run and returns its id and cancellation flag
+    ///
+    /// Runs started without an id get a generated one, so every run can be cancelled.
+    fn register(&self, run_id: Option<String>) -> Result<(String, Arc<AtomicBool>), String> {
+        let run_id = run_id
+            .unwrap_or_else(|| format!("profiler-run-{}", self.next_id.fetch_add(1, Ordering::SeqCst) + 1));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let mut runs = self.runs.lock().unwrap();
+        if runs.contains_key(&run_id) {
+            return Err(format!("A profiler run with id {} is already running", run_id));
+        }
+        runs.insert(run_id.clone(), cancelled.clone());
+        Ok((run_id, cancelled))
+    }
+
+    fn unregister(&self, run_id: &str) {
+        self.runs.lock().unwrap().remove(run_id);
+    }
 }
 
 /// Retrieves default configuration settings for the profiler
@@ -50,13 +155,10 @@ pub struct ProfilerConfig {
 ///   - 1 GiB data size
 ///   - 10 seconds duration
 ///   - No custom data file path
+///   - Not read-only
 #[command]
 pub async fn get_default_config() -> ProfilerConfig {
-    ProfilerConfig {
-        data_size: 1, // Default 1 GiB
-        duration: 10, // Default 10 seconds
-        data_file: None, // No custom path by default
-    }
+    ProfilerConfig::default()
 }
 
 /// Executes a profiling run with specified parameters
@@ -67,15 +169,21 @@ pub async fn get_default_config() -> ProfilerConfig {
 /// 
 /// # Arguments
 /// 
-/// * `app` - Tauri application handle for resource access
+/// * `app` - Tauri application handle for resource access and progress events
 /// * `nonces` - Number of nonces to use (must be multiple of 16)
 /// * `threads` - Number of threads to utilize
 /// * `config` - Optional custom configuration settings
+/// * `run_id` - Optional id under which the run can be cancelled, generated if omitted
 /// 
 /// # Returns
 /// 
 /// * `Ok(ProfilerResult)` - Results and metrics from the profiling run
-/// * `Err(String)` - Error message if profiling fails
+/// * `Err(String)` - Error message if profiling fails or is cancelled
+/// 
+/// # Events
+/// 
+/// * 'profiler-progress' - When the profiler starts, every second and for every line
+///   it prints, with the id of the run
 /// 
 /// # Validation
 /// 
@@ -88,6 +196,8 @@ pub async fn run_profiler(
     nonces: u32,
     threads: u32,
     config: Option<ProfilerConfig>,
+    run_id: Option<String>,
+    runs: State<'_, ProfilerRuns>,
 ) -> Result<ProfilerResult, String> {
     // Validate required parameters
     if nonces == 0 {
@@ -100,23 +210,301 @@ pub async fn run_profiler(
     }
 
     // Use default config if none provided
-    let config = config.unwrap_or_else(|| ProfilerConfig {
-        data_size: 1,
-        duration: 10,
-        data_file: None,
+    let config = config.unwrap_or_default();
+
+    let profiler_path = profiler_binary(&app)?;
+    let data_file = data_file_path(&config)?;
+    let data_file_existed = data_file.exists();
+
+    let (run_id, cancelled) = runs.register(run_id)?;
+    let run = RunContext { app, run_id: run_id.clone(), cancelled };
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let result = run_point(&run, &profiler_path, nonces, threads, &data_file, &config);
+        cleanup_data_file(&data_file, &config, data_file_existed, result.is_err());
+        result
+    })
+    .await
+    .map_err(|e| format!("Failed to run profiler: {}", e))
+    .and_then(|result| result);
+    runs.unregister(&run_id);
+    result
+}
+
+/// Cancels a running profiler run or sweep
+/// 
+/// The profiler process is killed, its temporary data file removed and the run
+/// fails with a cancellation error.
+/// 
+/// # Arguments
+/// 
+/// * `run_id` - Id the run was started with
+/// 
+/// # Returns
+/// 
+/// * `Ok(())` - If the run was found and flagged for cancellation
+/// * `Err(String)` - If no run with this id is running
+#[command]
+pub fn cancel_profiler(run_id: String, runs: State<'_, ProfilerRuns>) -> Result<(), String> {
+    let runs = runs.runs.lock().unwrap();
+    let cancelled = runs
+        .get(&run_id)
+        .ok_or_else(|| format!("No profiler run with id {} is running", run_id))?;
+    cancelled.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Runs the profiler against the data of an initialized PoS directory
+///
+/// Profiles the largest complete `postdata_N.bin` of the directory, so the
+/// measured speed reflects the disk and the data the node proves from instead
+/// of a temporary file. The file is never removed.
+///
+/// The profiler binary cannot be told to only read the file, so it is not
+/// protected from writes. The run is refused before it starts if the data size
+/// is larger than the file, as the profiler would extend it, and fails if the
+/// file was modified anyway, see [`FileSnapshot`].
+///
+/// # Arguments
+///
+/// * `app` - Tauri application handle for resource access and progress events
+/// * `dir` - Initialized PoS data directory
+/// * `nonces` - Number of nonces to use (must be multiple of 16)
+/// * `threads` - Number of threads to use
+/// * `config` - Optional duration and data size; the data size defaults to the whole GiB of the file
+/// * `run_id` - Optional id under which the run can be cancelled with [`cancel_profiler`], generated if omitted
+///
+/// # Returns
+///
+/// * `Ok(ProfilerResult)` - Profiling results with the data file used
+/// * `Err(String)` - If the directory has no complete data file of at least 1 GiB, the data
+///   size is zero or larger than the file, the file was modified, or profiling fails
+///
+/// # Events
+///
+/// * 'profiler-progress' - When the profiler starts, every second and for every line
+///   it prints, with the id of the run
+#[command]
+pub async fn run_profiler_on_pos_data(
+    app: tauri::AppHandle,
+    dir: String,
+    nonces: u32,
+    threads: u32,
+    config: Option<ProfilerConfig>,
+    run_id: Option<String>,
+    runs: State<'_, ProfilerRuns>,
+) -> Result<ProfilerResult, String> {
+    let report = inspect_pos_directory(dir.clone(), None)?;
+    if matches!(report.state, DirectoryState::Empty | DirectoryState::Inconsistent) {
+        return Err(format!("{} does not hold consistent PoS data", dir));
+    }
+    let file = report
+        .files
+        .iter()
+        .filter(|file| file.status == FileStatus::Complete)
+        .max_by_key(|file| file.expected_size)
+        .ok_or_else(|| format!("{} has no complete data file", dir))?;
+    let data_file = Path::new(&dir).join(format!("postdata_{}.bin", file.index));
+
+    // The profiler reads whole GiB, smaller files would have to be extended
+    let whole_gib = u32::try_from(file.expected_size / (1024 * 1024 * 1024)).unwrap_or(u32::MAX);
+    if whole_gib == 0 {
+        return Err(format!(
+            "Data file {} has {} bytes, profiling it read-only needs at least 1 GiB",
+            data_file.display(),
+            file.expected_size
+        ));
+    }
+    let config = config.unwrap_or(ProfilerConfig {
+        data_size: whole_gib,
+        ..ProfilerConfig::default()
     });
+    if config.data_size == 0 || config.data_size > whole_gib {
+        return Err(format!(
+            "Data size of {} GiB does not fit data file {} of {} bytes, use 1 to {} GiB",
+            config.data_size,
+            data_file.display(),
+            file.expected_size,
+            whole_gib
+        ));
+    }
+    let config = ProfilerConfig {
+        data_file: Some(data_file.to_string_lossy().to_string()),
+        read_only: true,
+        ..config
+    };
+    run_profiler(app, nonces, threads, Some(config), run_id, runs).await
+}
 
-    // Use custom data file path if provided, otherwise create temporary directory
-    let data_file = if let Some(path) = &config.data_file {
-        std::path::PathBuf::from(path)
-    } else {
-        let temp_dir = std::env::temp_dir().join("sm-init-profiler");
-        if !temp_dir.exists() {
-            std::fs::create_dir_all(&temp_dir).map_err(|e| e.to_string())?;
+/// Inclusive range of values swept by [`run_profiler_sweep`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct SweepRange {
+    pub start: u32,
+    pub end: u32,
+    pub step: u32,
+}
+
+impl SweepRange {
+    /// Values of the range from `start` to `end` in increments of `step`
+    pub fn values(&self) -> Vec<u32> {
+        (self.start..=self.end).step_by(self.step.max(1) as usize).collect()
+    }
+}
+
+/// Payload of the 'profiler-sweep-progress' event
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepProgress {
+    /// Number of points measured so far
+    pub completed: u32,
+    /// Number of points of the sweep
+    pub total: u32,
+    /// Result of the point that just finished
+    pub result: ProfilerResult,
+}
+
+/// Results of [`run_profiler_sweep`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfilerSweepResult {
+    /// One result per (nonces, threads) point, ordered by nonces, then threads
+    pub results: Vec<ProfilerResult>,
+    /// Fastest point for every nonce count, ordered by nonces
+    pub fastest: Vec<ProfilerResult>,
+}
+
+/// Runs the profiler over ranges of nonces and threads
+///
+/// The data file is generated by the first run and reused by all following
+/// ones, so the points differ only in their parameters. A temporary data file
+/// is removed when the sweep ends, also if a point fails.
+///
+/// # Arguments
+///
+/// * `app` - Tauri application handle for resource access and progress events
+/// * `nonces` - Range of nonce counts, all multiples of 16
+/// * `threads` - Range of thread counts
+/// * `config` - Optional custom configuration settings, shared by all points
+/// * `run_id` - Optional id under which the sweep can be cancelled, generated if omitted
+///
+/// # Returns
+///
+/// * `Ok(ProfilerSweepResult)` - Result matrix and the fastest point per nonce count
+/// * `Err(String)` - Error message if the ranges are invalid, a point fails or the sweep is cancelled
+///
+/// # Events
+///
+/// * 'profiler-progress' - While a point is running
+/// * 'profiler-sweep-progress' - After every point
+#[command]
+pub async fn run_profiler_sweep(
+    app: tauri::AppHandle,
+    nonces: SweepRange,
+    threads: SweepRange,
+    config: Option<ProfilerConfig>,
+    run_id: Option<String>,
+    runs: State<'_, ProfilerRuns>,
+) -> Result<ProfilerSweepResult, String> {
+    let nonce_values = nonces.values();
+    let thread_values = threads.values();
+    if nonces.step == 0 || threads.step == 0 {
+        return Err("Sweep steps must be greater than 0".to_string());
+    }
+    if nonce_values.is_empty() || thread_values.is_empty() {
+        return Err("Sweep ranges must not be empty".to_string());
+    }
+    if nonce_values.iter().any(|n| *n == 0 || n % 16 != 0) {
+        return Err("Nonces must be multiples of 16".to_string());
+    }
+    if thread_values.contains(&0) {
+        return Err("Threads must be greater than 0".to_string());
+    }
+
+    let points: Vec<(u32, u32)> = nonce_values
+        .iter()
+        .flat_map(|nonces| thread_values.iter().map(move |threads| (*nonces, *threads)))
+        .collect();
+    let results = run_points(app, points, config, run_id, &runs).await?;
+    Ok(ProfilerSweepResult {
+        fastest: fastest_per_nonces(&results),
+        results,
+    })
+}
+
+/// Runs the profiler once per (nonces, threads) point against the same data file
+///
+/// Shared by the sweep and the proving optimizer. A temporary data file is
+/// removed when the last point finished, also if a point fails.
+///
+/// # Events
+///
+/// * 'profiler-progress' - While a point is running
+/// * 'profiler-sweep-progress' - After every point
+pub(crate) async fn run_points(
+    app: tauri::AppHandle,
+    points: Vec<(u32, u32)>,
+    config: Option<ProfilerConfig>,
+    run_id: Option<String>,
+    runs: &ProfilerRuns,
+) -> Result<Vec<ProfilerResult>, String> {
+    let config = config.unwrap_or_default();
+    let profiler_path = profiler_binary(&app)?;
+    let data_file = data_file_path(&config)?;
+    let data_file_existed = data_file.exists();
+
+    let (run_id, cancelled) = runs.register(run_id)?;
+    let run = RunContext { app, run_id: run_id.clone(), cancelled };
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let total = points.len() as u32;
+        let mut results = Vec::new();
+        let mut outcome = Ok(());
+        for (nonces, threads) in points {
+            match run_point(&run, &profiler_path, nonces, threads, &data_file, &config) {
+                Ok(result) => {
+                    results.push(result.clone());
+                    let progress = SweepProgress {
+                        completed: results.len() as u32,
+                        total,
+                        result,
+                    };
+                    if let Err(e) = run.app.emit_all("profiler-sweep-progress", progress) {
+                        eprintln!("Failed to emit profiler-sweep-progress event: {}", e);
+                    }
+                }
+                Err(e) if run.is_cancelled() => {
+                    outcome = Err(e);
+                    break;
+                }
+                Err(e) => {
+                    outcome = Err(format!("Profiler failed with {} nonces and {} threads: {}", nonces, threads, e));
+                    break;
+                }
+            }
         }
-        temp_dir.join("profiler-data")
-    };
 
+        cleanup_data_file(&data_file, &config, data_file_existed, outcome.is_err());
+        outcome.map(|_| results)
+    })
+    .await
+    .map_err(|e| format!("Failed to run profiler sweep: {}", e))
+    .and_then(|result| result);
+    runs.unregister(&run_id);
+    result
+}
+
+/// Picks the point with the highest speed for every nonce count
+fn fastest_per_nonces(results: &[ProfilerResult]) -> Vec<ProfilerResult> {
+    let mut fastest: Vec<ProfilerResult> = Vec::new();
+    for result in results {
+        match fastest.iter_mut().find(|f| f.nonces == result.nonces) {
+            Some(best) if result.speed_gib_s > best.speed_gib_s => *best = result.clone(),
+            Some(_) => {}
+            None => fastest.push(result.clone()),
+        }
+    }
+    fastest.sort_by_key(|result| result.nonces);
+    fastest
+}
+
+/// Path of the profiler binary bundled with the application
+fn profiler_binary(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     // Get the path to the bundled profiler binary
     let resource_path = app
         .path_resolver()
@@ -135,28 +523,241 @@ pub async fn run_profiler(
     if !profiler_path.exists() {
         return Err(format!("Profiler binary not found at {:?}", profiler_path));
     }
+    Ok(profiler_path)
+}
+
+/// Data file of a profiling run
+///
+/// Uses the custom data file path if provided, otherwise a file in a temporary directory.
+/// Fails if the run could overwrite a file it must not touch, see [`check_data_file`].
+fn data_file_path(config: &ProfilerConfig) -> Result<PathBuf, String> {
+    if let Some(path) = &config.data_file {
+        let data_file = PathBuf::from(path);
+        check_data_file(&data_file, config)?;
+        Ok(data_file)
+    } else if config.read_only {
+        Err("Read-only profiler runs need an existing data file".to_string())
+    } else {
+        let data_file = temp_data_file();
+        if let Some(temp_dir) = data_file.parent().filter(|dir| !dir.exists()) {
+            std::fs::create_dir_all(temp_dir).map_err(|e| e.to_string())?;
+        }
+        Ok(data_file)
+    }
+}
+
+/// Checks that a run leaves a custom data file intact where it has to
+///
+/// Files of a PoS data directory are refused unless the run is read-only. A
+/// read-only run needs a file of at least the data size, as the profiler only
+/// writes to files that are smaller.
+fn check_data_file(data_file: &Path, config: &ProfilerConfig) -> Result<(), String> {
+    if config.read_only {
+        let size = std::fs::metadata(data_file)
+            .map_err(|e| format!("Failed to read data file {}: {}", data_file.display(), e))?
+            .len();
+        let data_size = config.data_size as u64 * 1024 * 1024 * 1024;
+        if config.data_size == 0 || size < data_size {
+            return Err(format!(
+                "Data file {} has {} bytes, a read-only run needs at least the data size of {} GiB",
+                data_file.display(),
+                size,
+                config.data_size
+            ));
+        }
+    } else if is_pos_data_file(data_file) {
+        return Err(format!(
+            "{} belongs to PoS data and would be overwritten by the profiler, profile it read-only instead",
+            data_file.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Whether a path names a file PostCLI writes to a PoS data directory
+fn is_pos_data_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            parse_data_file_name(name).is_some()
+                || [METADATA_FILE, IDENTITY_KEY_FILE, KEY_BIN_FILE].contains(&name)
+        })
+}
+
+/// Data file used if the configuration has no custom path
+pub(crate) fn temp_data_file() -> PathBuf {
+    std::env::temp_dir().join("sm-init-profiler").join("profiler-data")
+}
+
+/// Removes the data file of a finished run unless it should be kept
+///
+/// Temporary data files are always removed. A custom data file is kept for
+/// later runs, unless this run created it and failed, leaving it incomplete.
+/// Read-only data files are never removed.
+fn cleanup_data_file(data_file: &Path, config: &ProfilerConfig, existed: bool, failed: bool) {
+    if config.read_only {
+        return;
+    }
+    if config.data_file.is_none() || (failed && !existed) {
+        let _ = std::fs::remove_file(data_file);
+    }
+}
+
+/// Size and modification time of a read-only data file before a run
+///
+/// Compared with the file after the run to detect that the profiler wrote to
+/// it, as it cannot be told to open the file read-only.
+#[derive(Debug, Clone, PartialEq)]
+struct FileSnapshot {
+    len: u64,
+    modified: SystemTime,
+}
+
+impl FileSnapshot {
+    fn take(path: &Path) -> Result<FileSnapshot, String> {
+        let metadata = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to read data file {}: {}", path.display(), e))?;
+        let modified = metadata
+            .modified()
+            .map_err(|e| format!("Failed to read modification time of {}: {}", path.display(), e))?;
+        Ok(FileSnapshot { len: metadata.len(), modified })
+    }
+
+    /// Fails if the file no longer matches the snapshot
+    fn verify(&self, path: &Path) -> Result<(), String> {
+        let current = FileSnapshot::take(path)?;
+        if current != *self {
+            return Err(format!(
+                "Read-only data file {} was modified during profiling ({} bytes before, {} bytes after), check the PoS data",
+                path.display(),
+                self.len,
+                current.len
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// State shared by the profiler invocations of one run or sweep
+struct RunContext {
+    app: tauri::AppHandle,
+    run_id: String,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl RunContext {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Emits a 'profiler-progress' event
+    fn emit_progress(&self, nonces: u32, threads: u32, started: Instant, line: Option<String>) {
+        let progress = ProfilerProgress {
+            run_id: self.run_id.clone(),
+            nonces,
+            threads,
+            elapsed_s: started.elapsed().as_secs_f64(),
+            line,
+        };
+        if let Err(e) = self.app.emit_all("profiler-progress", progress) {
+            eprintln!("Failed to emit profiler-progress event: {}", e);
+        }
+    }
+}
+
+/// Runs the profiler binary once and parses its output
+///
+/// Blocks until the profiler exits or the run is cancelled, in which case the
+/// profiler is killed. A read-only run fails if the data file was modified.
+fn run_point(
+    run: &RunContext,
+    profiler_path: &Path,
+    nonces: u32,
+    threads: u32,
+    data_file: &Path,
+    config: &ProfilerConfig,
+) -> Result<ProfilerResult, String> {
+    let snapshot = if config.read_only {
+        Some(FileSnapshot::take(data_file)?)
+    } else {
+        None
+    };
 
     // Run profiler
-    let output = std::process::Command::new(&profiler_path)
+    let mut child = Command::new(profiler_path)
         .arg("--threads")
         .arg(threads.to_string())
         .arg("--nonces")
         .arg(nonces.to_string())
         .arg("--data-file")
-        .arg(&data_file)
+        .arg(data_file)
         .arg("--data-size")
         .arg(config.data_size.to_string())
         .arg("--duration")
         .arg(config.duration.to_string())
-        .output()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|e| format!("Failed to run profiler: {}", e))?;
 
-    if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    let (sender, receiver) = mpsc::channel();
+    let readers = vec![
+        child.stdout.take().map(|pipe| spawn_line_reader(pipe, true, sender.clone())),
+        child.stderr.take().map(|pipe| spawn_line_reader(pipe, false, sender.clone())),
+    ];
+    drop(sender);
+
+    let started = Instant::now();
+    // Reports the run id right away, also if the run was started without one
+    run.emit_progress(nonces, threads, started, None);
+    let mut last_progress = started;
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut collect = |(is_stdout, line): (bool, String)| {
+        run.emit_progress(nonces, threads, started, Some(line.clone()));
+        if is_stdout {
+            stdout.push(line);
+        } else {
+            stderr.push(line);
+        }
+    };
+
+    let status = loop {
+        receiver.try_iter().for_each(&mut collect);
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|e| format!("Failed to wait for profiler: {}", e))?
+        {
+            break status;
+        }
+        if run.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            if let Some(snapshot) = &snapshot {
+                snapshot.verify(data_file)?;
+            }
+            return Err("Profiler run was cancelled".to_string());
+        }
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            run.emit_progress(nonces, threads, started, None);
+        }
+        thread::sleep(RUN_POLL_INTERVAL);
+    };
+    for reader in readers.into_iter().flatten() {
+        let _ = reader.join();
+    }
+    receiver.try_iter().for_each(&mut collect);
+    if let Some(snapshot) = &snapshot {
+        snapshot.verify(data_file)?;
+    }
+
+    if !status.success() {
+        return Err(stderr.join("\n"));
     }
 
     // Parse profiler output
-    let parsed_output: serde_json::Value = serde_json::from_slice(&output.stdout)
+    let parsed_output: serde_json::Value = serde_json::from_str(&stdout.join("\n"))
         .map_err(|e| format!("Failed to parse profiler output: {}", e))?;
 
     // Extract values from parsed output
@@ -167,27 +768,42 @@ pub async fn run_profiler(
         .as_f64()
         .ok_or_else(|| "Missing speed_gib_s in output".to_string())?;
 
-    // Only cleanup if using temporary file
-    if config.data_file.is_none() {
-        let _ = std::fs::remove_file(data_file);
-    }
-
-    Ok(ProfilerResult {
+    let result = ProfilerResult {
         nonces,
         threads,
         time_s,
         speed_gib_s,
         data_size: config.data_size,
         duration: config.duration,
-        data_file: config.data_file,
+        data_file: config.data_file.clone(),
+        read_only: config.read_only,
+    };
+    if let Some(history) = run.app.try_state::<ProfilerHistory>() {
+        history.record(&result, data_file);
+    }
+    Ok(result)
+}
+
+/// Forwards the lines of a profiler pipe to a channel in a background thread
+fn spawn_line_reader<R: Read + Send + 'static>(
+    pipe: R,
+    is_stdout: bool,
+    sender: Sender<(bool, String)>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            if sender.send((is_stdout, line)).is_err() {
+                break;
+            }
+        }
     })
 }
 
-// Helper function to calculate probability of finding PoST in one pass
 /// Calculates the probability of finding a valid PoST in one pass
 /// 
-/// This function implements a simplified probability calculation for
-/// Proof of Space-Time success based on the number of nonces.
+/// Uses the exact binomial model of the proving module with MainNet parameters
+/// and the minimum number of space units:
+/// 1-(1-(1-BINOM.DIST(K2-1,N,K1/N,TRUE)))^nonces
 /// 
 /// # Arguments
 /// 
@@ -199,17 +815,100 @@ pub async fn run_profiler(
 /// 
 /// # Implementation Notes
 /// 
-/// Uses a simplified approximation of the full binomial probability formula:
-/// 1-(1-(1-BINOM.DIST(36,10^9,26/10^9,TRUE)))^nonces
-/// 
-/// The base probability (0.0124) is calibrated for 16 nonces and scaled
-/// based on the input nonce count.
+/// Use `calculate_proving_probability` for other networks, data sizes and the
+/// expected number of passes.
 #[command]
 pub fn calculate_post_probability(nonces: u32) -> f64 {
-    // Using simplified probability calculation
-    // Full formula: 1-(1-(1-BINOM.DIST(36,10^9,26/10^9,TRUE)))^nonces
-    // This is a simplified approximation
-    let base_prob: f64 = 0.0124; // Probability for 16 nonces
-    let groups = nonces as f64 / 16.0;
-    1.0f64 - (1.0f64 - base_prob).powf(groups)
+    PostParams::default().probability(nonces, 1).pass_probability
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::temp_dir;
+
+    fn result(nonces: u32, threads: u32, speed_gib_s: f64) -> ProfilerResult {
+        ProfilerResult {
+            nonces,
+            threads,
+            time_s: 10.0,
+            speed_gib_s,
+            data_size: 1,
+            duration: 10,
+            data_file: None,
+            read_only: false,
+        }
+    }
+
+    fn config(data_file: &Path, data_size: u32, read_only: bool) -> ProfilerConfig {
+        ProfilerConfig {
+            data_size,
+            duration: 10,
+            data_file: Some(data_file.to_string_lossy().to_string()),
+            read_only,
+        }
+    }
+
+    #[test]
+    fn refuses_pos_data_files() {
+        let dir = temp_dir("profiler-pos");
+        for name in ["postdata_0.bin", "postdata_metadata.json", "key.bin", "identity.key"] {
+            assert!(check_data_file(&dir.join(name), &config(&dir.join(name), 1, false)).is_err());
+        }
+        let data_file = dir.join("profiler-data");
+        assert!(check_data_file(&data_file, &config(&data_file, 1, false)).is_ok());
+    }
+
+    #[test]
+    fn read_only_needs_large_enough_file() {
+        let data_file = temp_dir("profiler-read-only").join("postdata_0.bin");
+        assert!(check_data_file(&data_file, &config(&data_file, 1, true)).is_err());
+        std::fs::write(&data_file, b"data").unwrap();
+        assert!(check_data_file(&data_file, &config(&data_file, 1, true)).is_err());
+        std::fs::File::create(&data_file).unwrap().set_len(1024 * 1024 * 1024).unwrap();
+        assert!(check_data_file(&data_file, &config(&data_file, 1, true)).is_ok());
+        cleanup_data_file(&data_file, &config(&data_file, 1, true), true, true);
+        assert!(data_file.exists());
+        std::fs::remove_dir_all(data_file.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn detects_modified_read_only_file() {
+        let dir = temp_dir("profiler-snapshot");
+        let data_file = dir.join("postdata_0.bin");
+        std::fs::write(&data_file, b"data").unwrap();
+        let snapshot = FileSnapshot::take(&data_file).unwrap();
+        assert!(snapshot.verify(&data_file).is_ok());
+
+        let file = std::fs::File::options().write(true).open(&data_file).unwrap();
+        file.set_modified(snapshot.modified + Duration::from_secs(1)).unwrap();
+        assert!(snapshot.verify(&data_file).is_err());
+        file.set_len(8).unwrap();
+        file.set_modified(snapshot.modified).unwrap();
+        assert!(snapshot.verify(&data_file).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn expands_sweep_ranges() {
+        let range = SweepRange { start: 16, end: 64, step: 16 };
+        assert_eq!(range.values(), vec![16, 32, 48, 64]);
+        let range = SweepRange { start: 1, end: 4, step: 2 };
+        assert_eq!(range.values(), vec![1, 3]);
+    }
+
+    #[test]
+    fn picks_fastest_point_per_nonce_count() {
+        let results = vec![
+            result(32, 1, 0.5),
+            result(32, 2, 0.9),
+            result(16, 1, 1.2),
+            result(16, 2, 1.1),
+        ];
+        let fastest: Vec<(u32, u32)> = fastest_per_nonces(&results)
+            .iter()
+            .map(|r| (r.nonces, r.threads))
+            .collect();
+        assert_eq!(fastest, vec![(16, 1), (32, 2)]);
+    }
 }
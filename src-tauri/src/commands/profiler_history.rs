@@ -0,0 +1,431 @@
+//! Profiler History Module
+//!
+//! This module persists every successful profiler run in the application data
+//! directory, together with a fingerprint of the hardware it ran on: CPU model,
+//! core count, memory, operating system and the directory of the data file.
+//!
+//! Results only carry over to runs with the same fingerprint, as the proving
+//! speed depends on the CPU and the disk as much as on nonces and threads. The
+//! history can be listed, filtered, deleted and exported as CSV or JSON, and a
+//! cached result can be reused instead of profiling again.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::cpu::{cpu_model, get_cpu_cores};
+use super::profiler::{temp_data_file, ProfilerConfig, ProfilerResult};
+
+/// Name of the file holding the history in the app data directory
+const HISTORY_FILE: &str = "profiler-history.json";
+
+/// Maximum number of entries kept, older entries are dropped first
+const MAX_ENTRIES: usize = 1000;
+
+/// Hardware a profiler result was measured on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HardwareFingerprint {
+    pub cpu_model: String,
+    /// Logical CPU cores
+    pub cores: usize,
+    /// Total memory in MiB
+    pub memory_mib: u64,
+    pub os: String,
+    /// Directory holding the profiler data file
+    pub data_location: String,
+}
+
+impl HardwareFingerprint {
+    /// Fingerprint of this machine for a profiler data file
+    pub fn current(data_file: &Path) -> HardwareFingerprint {
+        let os = match (sys_info::os_type(), sys_info::os_release()) {
+            (Ok(os_type), Ok(release)) => format!("{} {}", os_type, release),
+            _ => std::env::consts::OS.to_string(),
+        };
+        HardwareFingerprint {
+            cpu_model: cpu_model().unwrap_or_else(|| "unknown".to_string()),
+            cores: get_cpu_cores(),
+            memory_mib: sys_info::mem_info().map(|mem| mem.total / 1024).unwrap_or(0),
+            os,
+            data_location: data_file
+                .parent()
+                .map(|dir| dir.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Profiler run stored in the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfilerHistoryEntry {
+    /// Stable identifier of the entry, derived from the recording time
+    pub id: String,
+    /// Recording time as seconds since the Unix epoch
+    pub recorded_at: u64,
+    pub fingerprint: HardwareFingerprint,
+    pub result: ProfilerResult,
+}
+
+/// Criteria of [`list_profiler_history`], unset fields match every entry
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HistoryFilter {
+    pub nonces: Option<u32>,
+    pub threads: Option<u32>,
+    /// Only entries measured on exactly this hardware
+    pub fingerprint: Option<HardwareFingerprint>,
+    /// Only entries with this CPU model
+    pub cpu_model: Option<String>,
+    /// Only entries recorded at or after this time, in seconds since the Unix epoch
+    pub since: Option<u64>,
+    /// Only entries recorded at or before this time, in seconds since the Unix epoch
+    pub until: Option<u64>,
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, entry: &ProfilerHistoryEntry) -> bool {
+        self.nonces.is_none_or(|nonces| entry.result.nonces == nonces)
+            && self.threads.is_none_or(|threads| entry.result.threads == threads)
+            && self.fingerprint.as_ref().is_none_or(|fingerprint| entry.fingerprint == *fingerprint)
+            && self.cpu_model.as_ref().is_none_or(|model| entry.fingerprint.cpu_model == *model)
+            && self.since.is_none_or(|since| entry.recorded_at >= since)
+            && self.until.is_none_or(|until| entry.recorded_at <= until)
+    }
+}
+
+/// Tauri-managed profiler history backed by a JSON file
+pub struct ProfilerHistory {
+    path: Option<PathBuf>,
+    entries: Mutex<Vec<ProfilerHistoryEntry>>,
+}
+
+impl ProfilerHistory {
+    /// Loads the history from the application data directory
+    ///
+    /// A missing or unreadable file results in an empty history, a corrupt file
+    /// is logged and replaced on the next write. If the data directory cannot
+    /// be determined, entries are only kept in memory.
+    pub fn load(app: &tauri::AppHandle) -> ProfilerHistory {
+        let path = app.path_resolver().app_data_dir().map(|dir| dir.join(HISTORY_FILE));
+        let entries = path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok().map(|content| (p, content)))
+            .and_then(|(p, content)| match serde_json::from_str(&content) {
+                Ok(entries) => Some(entries),
+                Err(e) => {
+                    eprintln!("Failed to parse profiler history {:?}: {}", p, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        ProfilerHistory {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Records the result of a run with the data file it used and persists the history
+    pub fn record(&self, result: &ProfilerResult, data_file: &Path) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let entry = ProfilerHistoryEntry {
+            id: format!("{}-{}-{}", now.as_millis(), result.nonces, result.threads),
+            recorded_at: now.as_secs(),
+            fingerprint: HardwareFingerprint::current(data_file),
+            result: result.clone(),
+        };
+        let mut entries = self.entries.lock().unwrap();
+        entries.push(entry);
+        if entries.len() > MAX_ENTRIES {
+            let excess = entries.len() - MAX_ENTRIES;
+            entries.drain(..excess);
+        }
+        self.persist(&entries);
+    }
+
+    /// Returns copies of all entries matching a filter, oldest first
+    pub fn filter(&self, filter: &HistoryFilter) -> Vec<ProfilerHistoryEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| filter.matches(entry))
+            .cloned()
+            .collect()
+    }
+
+    /// Removes entries by id, persists the history and returns the number removed
+    pub fn remove(&self, ids: &[String]) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        let len = entries.len();
+        entries.retain(|entry| !ids.contains(&entry.id));
+        let removed = len - entries.len();
+        if removed > 0 {
+            self.persist(&entries);
+        }
+        removed
+    }
+
+    /// Writes the entries to disk
+    ///
+    /// The history is written to a temporary file first and renamed over the
+    /// previous one, so a crash while writing never leaves a truncated file.
+    fn persist(&self, entries: &[ProfilerHistoryEntry]) {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return,
+        };
+        let tmp_path = path.with_extension("json.tmp");
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| {
+                let content = serde_json::to_string_pretty(entries).map_err(std::io::Error::other)?;
+                fs::write(&tmp_path, content)?;
+                fs::rename(&tmp_path, path)
+            });
+        if let Err(e) = result {
+            eprintln!("Failed to persist profiler history to {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Lists the profiler history
+///
+/// # Arguments
+///
+/// * `filter` - Optional criteria the entries must match
+///
+/// # Returns
+///
+/// * `Vec<ProfilerHistoryEntry>` - Matching entries, oldest first
+#[tauri::command]
+pub fn list_profiler_history(
+    filter: Option<HistoryFilter>,
+    history: State<'_, ProfilerHistory>,
+) -> Vec<ProfilerHistoryEntry> {
+    history.filter(&filter.unwrap_or_default())
+}
+
+/// Deletes entries from the profiler history
+///
+/// # Arguments
+///
+/// * `ids` - Ids of the entries to delete
+///
+/// # Returns
+///
+/// * `usize` - Number of entries deleted
+#[tauri::command]
+pub fn delete_profiler_history(ids: Vec<String>, history: State<'_, ProfilerHistory>) -> usize {
+    history.remove(&ids)
+}
+
+/// Returns the fingerprint of this machine for a profiler configuration
+///
+/// # Arguments
+///
+/// * `config` - Profiler configuration, the temporary data file is assumed if omitted
+#[tauri::command]
+pub fn get_hardware_fingerprint(config: Option<ProfilerConfig>) -> HardwareFingerprint {
+    HardwareFingerprint::current(&profiler_data_file(config.as_ref()))
+}
+
+/// Finds the latest result of an equal run on the same hardware
+///
+/// A result is reused only if nonces, threads, data size, duration, data file
+/// and read-only mode match and it was measured with the same fingerprint,
+/// including the data file location.
+///
+/// # Arguments
+///
+/// * `nonces` - Number of nonces of the run
+/// * `threads` - Number of threads of the run
+/// * `config` - Profiler configuration of the run
+///
+/// # Returns
+///
+/// * `Option<ProfilerHistoryEntry>` - Latest matching entry, if any
+///
+/// # Example
+///
+/// ```rust
+/// if let Some(entry) = get_cached_profiler_result(288, 4, None, history) {
+///     println!("Cached speed: {} GiB/s", entry.result.speed_gib_s);
+/// }
+/// ```
+#[tauri::command]
+pub fn get_cached_profiler_result(
+    nonces: u32,
+    threads: u32,
+    config: Option<ProfilerConfig>,
+    history: State<'_, ProfilerHistory>,
+) -> Option<ProfilerHistoryEntry> {
+    let config = config.unwrap_or_default();
+    let filter = HistoryFilter {
+        nonces: Some(nonces),
+        threads: Some(threads),
+        fingerprint: Some(HardwareFingerprint::current(&profiler_data_file(Some(&config)))),
+        ..HistoryFilter::default()
+    };
+    history
+        .filter(&filter)
+        .into_iter()
+        .rev()
+        .find(|entry| {
+            entry.result.data_size == config.data_size
+                && entry.result.duration == config.duration
+                && entry.result.data_file == config.data_file
+                && entry.result.read_only == config.read_only
+        })
+}
+
+/// Exports the profiler history to a file
+///
+/// # Arguments
+///
+/// * `path` - File to write
+/// * `format` - `"csv"` or `"json"`
+/// * `filter` - Optional criteria the exported entries must match
+///
+/// # Returns
+///
+/// * `Ok(usize)` - Number of exported entries
+/// * `Err(String)` - If the format is unknown or the file cannot be written
+#[tauri::command]
+pub fn export_profiler_history(
+    path: String,
+    format: String,
+    filter: Option<HistoryFilter>,
+    history: State<'_, ProfilerHistory>,
+) -> Result<usize, String> {
+    let entries = history.filter(&filter.unwrap_or_default());
+    let content = match format.as_str() {
+        "csv" => to_csv(&entries),
+        "json" => serde_json::to_string_pretty(&entries)
+            .map_err(|e| format!("Failed to serialize profiler history: {}", e))?,
+        _ => return Err(format!("Unknown export format: {}", format)),
+    };
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(entries.len())
+}
+
+/// Data file a profiler run with this configuration uses
+fn profiler_data_file(config: Option<&ProfilerConfig>) -> PathBuf {
+    match config.and_then(|config| config.data_file.as_ref()) {
+        Some(path) => PathBuf::from(path),
+        None => temp_data_file(),
+    }
+}
+
+/// Renders entries as CSV with a header row
+fn to_csv(entries: &[ProfilerHistoryEntry]) -> String {
+    let mut csv = String::from(
+        "id,recorded_at,nonces,threads,speed_gib_s,time_s,data_size,duration,data_file,cpu_model,cores,memory_mib,os,data_location\n",
+    );
+    for entry in entries {
+        let (result, fingerprint) = (&entry.result, &entry.fingerprint);
+        let fields = [
+            csv_field(&entry.id),
+            entry.recorded_at.to_string(),
+            result.nonces.to_string(),
+            result.threads.to_string(),
+            result.speed_gib_s.to_string(),
+            result.time_s.to_string(),
+            result.data_size.to_string(),
+            result.duration.to_string(),
+            csv_field(result.data_file.as_deref().unwrap_or("")),
+            csv_field(&fingerprint.cpu_model),
+            fingerprint.cores.to_string(),
+            fingerprint.memory_mib.to_string(),
+            csv_field(&fingerprint.os),
+            csv_field(&fingerprint.data_location),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, recorded_at: u64, nonces: u32, cpu_model: &str) -> ProfilerHistoryEntry {
+        ProfilerHistoryEntry {
+            id: id.to_string(),
+            recorded_at,
+            fingerprint: HardwareFingerprint {
+                cpu_model: cpu_model.to_string(),
+                cores: 8,
+                memory_mib: 16384,
+                os: "Linux 6.1".to_string(),
+                data_location: "/data".to_string(),
+            },
+            result: ProfilerResult {
+                nonces,
+                threads: 4,
+                time_s: 10.0,
+                speed_gib_s: 1.5,
+                data_size: 1,
+                duration: 10,
+                data_file: Some("/data/profiler-data".to_string()),
+                read_only: false,
+            },
+        }
+    }
+
+    #[test]
+    fn filters_entries() {
+        let entries = [
+            entry("a", 100, 16, "CPU A"),
+            entry("b", 200, 32, "CPU A"),
+            entry("c", 300, 32, "CPU B"),
+        ];
+        let ids = |filter: HistoryFilter| -> Vec<String> {
+            entries
+                .iter()
+                .filter(|e| filter.matches(e))
+                .map(|e| e.id.clone())
+                .collect()
+        };
+        assert_eq!(ids(HistoryFilter::default()), vec!["a", "b", "c"]);
+        assert_eq!(ids(HistoryFilter { nonces: Some(32), ..HistoryFilter::default() }), vec!["b", "c"]);
+        assert_eq!(
+            ids(HistoryFilter {
+                fingerprint: Some(entries[0].fingerprint.clone()),
+                since: Some(150),
+                ..HistoryFilter::default()
+            }),
+            vec!["b"]
+        );
+        assert_eq!(
+            ids(HistoryFilter { cpu_model: Some("CPU B".to_string()), until: Some(250), ..HistoryFilter::default() }),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = to_csv(&[entry("a", 100, 16, "Intel(R) Core(TM) i7, \"8 cores\"")]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("id,recorded_at,nonces,threads,speed_gib_s"));
+        assert_eq!(
+            lines[1],
+            "a,100,16,4,1.5,10,1,10,/data/profiler-data,\"Intel(R) Core(TM) i7, \"\"8 cores\"\"\",8,16384,Linux 6.1,/data"
+        );
+    }
+}
@@ -0,0 +1,545 @@
+//! PoST Proving Probability Module
+//!
+//! This module models the chance of generating a PoST proof in one pass over the
+//! PoS data. During a pass every nonce is an independent trial: each of the
+//! `N = numUnits * labelsPerUnit` labels qualifies with probability `K1 / N`, and
+//! the nonce succeeds if at least `K2` labels qualify. A pass succeeds if any of
+//! its nonces does.
+//!
+//! The binomial tail is evaluated in log space, as `N` is in the order of 10^10
+//! and the individual terms underflow or lose all precision in plain arithmetic.
+//!
+//! On top of the probability model, the module estimates whether proving fits
+//! in the time available between two PoET cycles, given the CPU speed measured
+//! by the profiler and the read speed of the disk holding the data.
+
+use serde::{Deserialize, Serialize};
+
+use super::pos_data::BYTES_PER_LABEL;
+
+/// Length of the cycle gap in which the proof must be generated, in hours (network configuration).
+/// Fallback for callers that omit it; the frontend passes `SizeConstants.CYCLE_GAP_HOURS`
+pub const CYCLE_GAP_HOURS: f64 = 12.0;
+
+/// Share of the cycle gap that may be spent on proving, leaving a safety margin.
+/// Fallback for callers that omit it; the frontend passes `SizeConstants.K_SAFE_PERIOD`
+pub const K_SAFE_PERIOD: f64 = 0.7;
+
+/// Probability of finishing within the deadline above which proving is considered feasible
+pub const DEFAULT_TARGET_PROBABILITY: f64 = 0.99;
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// Largest K1 and K2 accepted, far above any network's, to bound the computation
+pub const MAX_K: u32 = 100_000;
+
+/// Network parameters of the PoST proving difficulty
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostParams {
+    /// Expected number of qualifying labels per nonce
+    pub k1: u32,
+    /// Number of qualifying labels required for a proof
+    pub k2: u32,
+    pub labels_per_unit: u64,
+    pub num_units: u32,
+}
+
+/// MainNet parameters with the minimum number of space units
+impl Default for PostParams {
+    fn default() -> Self {
+        PostParams {
+            k1: 26,
+            k2: 37,
+            labels_per_unit: 4_294_967_296,
+            num_units: 4,
+        }
+    }
+}
+
+/// Probability of finding a proof with a number of nonces
+#[derive(Debug, Clone, Serialize)]
+pub struct ProvingProbability {
+    /// Probability that a single nonce yields a proof
+    pub nonce_probability: f64,
+    /// Probability that one pass with all nonces yields a proof
+    pub pass_probability: f64,
+    /// Expected number of passes until a proof is found
+    pub expected_passes: f64,
+    /// Number of passes `probability_more_passes` refers to
+    pub passes: u32,
+    /// Probability that more than `passes` passes are needed
+    pub probability_more_passes: f64,
+}
+
+/// Input of [`estimate_proving_feasibility`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeasibilityInput {
+    pub num_units: u32,
+    pub nonces: u32,
+    /// Speed measured by the profiler for these nonces in GiB/s, ignored if not positive
+    pub cpu_speed_gib_s: Option<f64>,
+    /// Read speed of the disk holding the data in MiB/s, ignored if not positive
+    pub disk_read_mib_s: Option<f64>,
+    /// Cycle gap in hours, [`CYCLE_GAP_HOURS`] if omitted
+    pub cycle_gap_hours: Option<f64>,
+    /// Share of the cycle gap available for proving in (0, 1], [`K_SAFE_PERIOD`] if omitted
+    pub safe_period: Option<f64>,
+    /// Probability of finishing in time required for a feasible verdict, in [0, 1]
+    pub target_probability: Option<f64>,
+    /// Network parameters, MainNet if omitted; `num_units` is taken from the input
+    pub params: Option<PostParams>,
+}
+
+/// Whether proving is expected to finish within the safe part of the cycle gap
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// A proof is found in time with at least the target probability
+    Feasible,
+    /// At least one pass fits, but a proof may not be found in time
+    Risky,
+    /// Not even a single pass fits
+    Infeasible,
+}
+
+/// What limits the speed of a proving pass
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitingFactor {
+    Cpu,
+    Disk,
+}
+
+/// Result of [`estimate_proving_feasibility`]
+#[derive(Debug, Clone, Serialize)]
+pub struct FeasibilityEstimate {
+    pub verdict: Verdict,
+    pub limiting_factor: LimitingFactor,
+    /// Size of the PoS data read by every pass in bytes
+    pub data_size: u64,
+    /// Duration of one pass over the data in seconds
+    pub pass_time_s: f64,
+    pub pass_probability: f64,
+    pub expected_passes: f64,
+    /// Expected time until a proof is found in seconds
+    pub expected_total_s: f64,
+    /// Time available for proving in seconds
+    pub deadline_s: f64,
+    /// Number of complete passes that fit before the deadline
+    pub passes_in_deadline: u32,
+    /// Probability of finding a proof before the deadline
+    pub probability_in_deadline: f64,
+}
+
+impl FeasibilityInput {
+    /// Estimates the proving time and checks it against the deadline
+    pub fn estimate(&self) -> Result<FeasibilityEstimate, String> {
+        if self.nonces == 0 {
+            return Err("Nonces must be greater than 0".to_string());
+        }
+        let params = PostParams {
+            num_units: self.num_units,
+            ..self.params.unwrap_or_default()
+        };
+        params.validate()?;
+
+        let cycle_gap_hours = self.cycle_gap_hours.unwrap_or(CYCLE_GAP_HOURS);
+        if cycle_gap_hours.is_nan() || cycle_gap_hours <= 0.0 {
+            return Err("Cycle gap must be greater than 0".to_string());
+        }
+        let safe_period = self.safe_period.unwrap_or(K_SAFE_PERIOD);
+        if safe_period.is_nan() || safe_period <= 0.0 || safe_period > 1.0 {
+            return Err("Safe period must be greater than 0 and at most 1".to_string());
+        }
+        let target_probability = self
+            .target_probability
+            .unwrap_or(DEFAULT_TARGET_PROBABILITY);
+        if !(0.0..=1.0).contains(&target_probability) {
+            return Err("Target probability must be between 0 and 1".to_string());
+        }
+
+        // A missing or unmeasured (non-positive) speed does not limit the pass
+        let cpu = self
+            .cpu_speed_gib_s
+            .filter(|speed| *speed > 0.0)
+            .map(|speed| speed * GIB);
+        let disk = self
+            .disk_read_mib_s
+            .filter(|speed| *speed > 0.0)
+            .map(|speed| speed * MIB);
+        let (speed, limiting_factor) = match (cpu, disk) {
+            (Some(cpu), Some(disk)) if disk < cpu => (disk, LimitingFactor::Disk),
+            (Some(cpu), _) => (cpu, LimitingFactor::Cpu),
+            (None, Some(disk)) => (disk, LimitingFactor::Disk),
+            (None, None) => {
+                return Err(
+                    "A CPU speed or a disk read speed greater than 0 is required".to_string(),
+                )
+            }
+        };
+
+        let data_size = params.num_units as u64 * params.labels_per_unit * BYTES_PER_LABEL;
+        let pass_time_s = data_size as f64 / speed;
+        let deadline_s = cycle_gap_hours * 3600.0 * safe_period;
+        let passes_in_deadline = (deadline_s / pass_time_s).floor().min(u32::MAX as f64) as u32;
+
+        let probability = params.probability(self.nonces, passes_in_deadline);
+        let probability_in_deadline = 1.0 - probability.probability_more_passes;
+        let verdict = if passes_in_deadline == 0 {
+            Verdict::Infeasible
+        } else if probability_in_deadline >= target_probability {
+            Verdict::Feasible
+        } else {
+            Verdict::Risky
+        };
+
+        Ok(FeasibilityEstimate {
+            verdict,
+            limiting_factor,
+            data_size,
+            pass_time_s,
+            pass_probability: probability.pass_probability,
+            expected_passes: probability.expected_passes,
+            expected_total_s: pass_time_s * probability.expected_passes,
+            deadline_s,
+            passes_in_deadline,
+            probability_in_deadline,
+        })
+    }
+}
+
+impl PostParams {
+    /// Checks that the parameters describe a valid proving difficulty
+    pub fn validate(&self) -> Result<(), String> {
+        if self.k1 == 0 || self.k2 == 0 {
+            return Err("K1 and K2 must be greater than 0".to_string());
+        }
+        if self.k1 > MAX_K || self.k2 > MAX_K {
+            return Err(format!("K1 and K2 must not exceed {}", MAX_K));
+        }
+        if self.labels_per_unit == 0 || self.num_units == 0 {
+            return Err("Labels per unit and number of units must be greater than 0".to_string());
+        }
+        if self.num_labels() <= self.k2 as f64 || self.num_labels() <= self.k1 as f64 {
+            return Err("The data must hold more labels than K1 and K2".to_string());
+        }
+        Ok(())
+    }
+
+    /// Total number of labels of the data
+    fn num_labels(&self) -> f64 {
+        self.num_units as f64 * self.labels_per_unit as f64
+    }
+
+    /// Probability that a single nonce finds at least `k2` qualifying labels
+    ///
+    /// Sums the binomial probabilities of `0..k2` qualifying labels in log space
+    /// and returns the complement. The terms are accumulated as they are computed,
+    /// so memory use does not depend on K2.
+    pub fn nonce_probability(&self) -> f64 {
+        let n = self.num_labels();
+        let p = self.k1 as f64 / n;
+        let ln_p = p.ln();
+        let ln_q = (-p).ln_1p();
+
+        // ln P(X = k) = ln C(n, k) + k ln p + (n - k) ln(1 - p), with ln C(n, k) built incrementally
+        let mut ln_binomial = 0.0;
+        let mut ln_cdf = LogSumExp::default();
+        for k in 0..self.k2 {
+            if k > 0 {
+                ln_binomial += (n - (k - 1) as f64).ln() - (k as f64).ln();
+            }
+            ln_cdf.add(ln_binomial + k as f64 * ln_p + (n - k as f64) * ln_q);
+        }
+
+        let cdf = ln_cdf.value().exp();
+        (1.0 - cdf).clamp(0.0, 1.0)
+    }
+
+    /// Probability of finding a proof with `nonces` nonces in one pass and over several passes
+    ///
+    /// # Arguments
+    ///
+    /// * `nonces` - Number of nonces per pass
+    /// * `passes` - Number of passes for the probability of needing more of them
+    pub fn probability(&self, nonces: u32, passes: u32) -> ProvingProbability {
+        let nonce_probability = self.nonce_probability();
+        // 1 - (1 - p)^nonces without cancellation for small p
+        let ln_pass_failure = nonces as f64 * (-nonce_probability).ln_1p();
+        let pass_probability = -ln_pass_failure.exp_m1();
+
+        ProvingProbability {
+            nonce_probability,
+            pass_probability,
+            expected_passes: if pass_probability > 0.0 {
+                1.0 / pass_probability
+            } else {
+                f64::INFINITY
+            },
+            passes,
+            probability_more_passes: (passes as f64 * ln_pass_failure).exp(),
+        }
+    }
+}
+
+/// Accumulates `ln(sum(exp(x)))` over a stream of values without overflow or underflow
+///
+/// The sum is kept relative to the largest value seen so far and rescaled
+/// whenever a larger value arrives.
+struct LogSumExp {
+    max: f64,
+    sum: f64,
+}
+
+impl Default for LogSumExp {
+    fn default() -> Self {
+        LogSumExp {
+            max: f64::NEG_INFINITY,
+            sum: 0.0,
+        }
+    }
+}
+
+impl LogSumExp {
+    fn add(&mut self, value: f64) {
+        if value == f64::NEG_INFINITY {
+            return;
+        }
+        if value > self.max {
+            self.sum = self.sum * (self.max - value).exp() + 1.0;
+            self.max = value;
+        } else {
+            self.sum += (value - self.max).exp();
+        }
+    }
+
+    fn value(&self) -> f64 {
+        if self.max == f64::NEG_INFINITY {
+            return self.max;
+        }
+        self.max + self.sum.ln()
+    }
+}
+
+/// Calculates the probability of finding a PoST proof
+///
+/// # Arguments
+///
+/// * `nonces` - Number of nonces per pass
+/// * `params` - Network parameters and data size, MainNet with 4 units if omitted
+/// * `passes` - Number of passes for the probability of needing more of them, 1 if omitted
+///
+/// # Returns
+///
+/// * `Ok(ProvingProbability)` - Single-nonce and single-pass probabilities and expected passes
+/// * `Err(String)` - If the parameters are invalid
+///
+/// # Example
+///
+/// ```rust
+/// let probability = calculate_proving_probability(288, None, Some(3))?;
+/// println!("Expected passes: {:.2}", probability.expected_passes);
+/// ```
+#[tauri::command]
+pub fn calculate_proving_probability(
+    nonces: u32,
+    params: Option<PostParams>,
+    passes: Option<u32>,
+) -> Result<ProvingProbability, String> {
+    if nonces == 0 {
+        return Err("Nonces must be greater than 0".to_string());
+    }
+    let params = params.unwrap_or_default();
+    params.validate()?;
+    Ok(params.probability(nonces, passes.unwrap_or(1)))
+}
+
+/// Estimates whether proving finishes within the safe part of the cycle gap
+///
+/// Every pass reads the whole PoS data, at the lower of the CPU speed measured
+/// by the profiler and the disk read speed. The number of passes until a proof
+/// is found follows a geometric distribution with the single-pass probability.
+///
+/// # Arguments
+///
+/// * `input` - Data size, nonces, measured speeds and optional deadline parameters
+///
+/// # Returns
+///
+/// * `Ok(FeasibilityEstimate)` - Expected proving time, verdict and limiting factor
+/// * `Err(String)` - If no speed is given or the parameters are invalid
+///
+/// # Example
+///
+/// ```rust
+/// let estimate = estimate_proving_feasibility(input)?;
+/// println!("{:?}, limited by {:?}", estimate.verdict, estimate.limiting_factor);
+/// ```
+#[tauri::command]
+pub fn estimate_proving_feasibility(
+    input: FeasibilityInput,
+) -> Result<FeasibilityEstimate, String> {
+    input.estimate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {} within {}, got {}",
+            expected,
+            tolerance,
+            actual
+        );
+    }
+
+    #[test]
+    fn matches_small_binomial() {
+        // n = 10, p = 0.5: P(X >= 8) = (45 + 10 + 1) / 1024
+        let params = PostParams {
+            k1: 5,
+            k2: 8,
+            labels_per_unit: 10,
+            num_units: 1,
+        };
+        assert_close(params.nonce_probability(), 56.0 / 1024.0, 1e-12);
+    }
+
+    #[test]
+    fn approaches_poisson_tail_for_mainnet() {
+        // With n in the order of 10^10 the binomial is a Poisson(26) distribution
+        let mut term = (-26.0f64).exp();
+        let mut cdf = term;
+        for k in 1..37 {
+            term *= 26.0 / k as f64;
+            cdf += term;
+        }
+        assert_close(PostParams::default().nonce_probability(), 1.0 - cdf, 1e-9);
+    }
+
+    #[test]
+    fn combines_nonces_and_passes() {
+        let params = PostParams::default();
+        let p = params.nonce_probability();
+        let probability = params.probability(16, 3);
+
+        let pass = 1.0 - (1.0 - p).powi(16);
+        assert_close(probability.pass_probability, pass, 1e-12);
+        assert_close(probability.expected_passes, 1.0 / pass, 1e-9);
+        assert_close(
+            probability.probability_more_passes,
+            (1.0 - pass).powi(3),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_params() {
+        let params = PostParams {
+            k2: 0,
+            ..PostParams::default()
+        };
+        assert!(params.validate().is_err());
+        let params = PostParams {
+            labels_per_unit: 8,
+            num_units: 4,
+            ..PostParams::default()
+        };
+        assert!(params.validate().is_err());
+        let params = PostParams {
+            k2: u32::MAX,
+            ..PostParams::default()
+        };
+        assert!(params.validate().is_err());
+        assert!(calculate_proving_probability(0, None, None).is_err());
+    }
+
+    fn input(cpu_speed_gib_s: Option<f64>, disk_read_mib_s: Option<f64>) -> FeasibilityInput {
+        FeasibilityInput {
+            num_units: 4,
+            nonces: 288,
+            cpu_speed_gib_s,
+            disk_read_mib_s,
+            cycle_gap_hours: None,
+            safe_period: None,
+            target_probability: None,
+            params: None,
+        }
+    }
+
+    #[test]
+    fn estimates_feasible_proving() {
+        // 256 GiB at 1 GiB/s take 256 s per pass, 118 passes fit in 8.4 hours
+        let estimate = input(Some(1.0), Some(2048.0)).estimate().unwrap();
+        assert_eq!(estimate.limiting_factor, LimitingFactor::Cpu);
+        assert_close(estimate.pass_time_s, 256.0, 1e-9);
+        assert_eq!(estimate.passes_in_deadline, 118);
+        assert_eq!(estimate.verdict, Verdict::Feasible);
+        assert_close(
+            estimate.expected_total_s,
+            estimate.pass_time_s / estimate.pass_probability,
+            1e-6,
+        );
+    }
+
+    #[test]
+    fn detects_disk_bottleneck_and_infeasible_deadline() {
+        // 256 GiB at 8 MiB/s take 9.1 hours per pass
+        let estimate = input(Some(1.0), Some(8.0)).estimate().unwrap();
+        assert_eq!(estimate.limiting_factor, LimitingFactor::Disk);
+        assert_eq!(estimate.passes_in_deadline, 0);
+        assert_eq!(estimate.verdict, Verdict::Infeasible);
+    }
+
+    #[test]
+    fn reports_risky_deadline() {
+        // 2 hours per pass leave 4 passes of 16 nonces
+        let estimate = FeasibilityInput {
+            nonces: 16,
+            ..input(None, Some(256.0 * 1024.0 / 7200.0))
+        }
+        .estimate()
+        .unwrap();
+        assert_eq!(estimate.passes_in_deadline, 4);
+        assert_eq!(estimate.verdict, Verdict::Risky);
+    }
+
+    #[test]
+    fn requires_a_speed() {
+        assert!(input(None, None).estimate().is_err());
+        assert!(input(Some(0.0), None).estimate().is_err());
+        assert!(input(Some(f64::NAN), Some(0.0)).estimate().is_err());
+    }
+
+    #[test]
+    fn ignores_unmeasured_speeds() {
+        let estimate = input(Some(0.0), Some(1000.0)).estimate().unwrap();
+        assert_eq!(estimate.limiting_factor, LimitingFactor::Disk);
+        let estimate = input(Some(1.0), Some(-1.0)).estimate().unwrap();
+        assert_eq!(estimate.limiting_factor, LimitingFactor::Cpu);
+    }
+
+    #[test]
+    fn rejects_out_of_range_periods_and_probabilities() {
+        let cases = [
+            (0.0, 0.99),
+            (1.5, 0.99),
+            (0.7, -0.1),
+            (0.7, 1.1),
+            (f64::NAN, 0.99),
+        ];
+        for (safe_period, target_probability) in cases {
+            let input = FeasibilityInput {
+                safe_period: Some(safe_period),
+                target_probability: Some(target_probability),
+                ..input(Some(1.0), None)
+            };
+            assert!(input.estimate().is_err());
+        }
+    }
+}
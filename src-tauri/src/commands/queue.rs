@@ -0,0 +1,640 @@
+//! Initialization Queue Module
+//!
+//! This module provides a persistent queue of PostCLI initializations, for operators
+//! initializing several identities or PoS directories on one host. Each entry holds
+//! a full [`PostCliConfig`]; entries are started in queue order, with at most
+//! `max_parallel_per_provider` jobs running on the same provider at any time
+//! (one by default, i.e. sequentially).
+//!
+//! The queue is stored in the application data directory and survives restarts:
+//! entries whose job was re-attached keep running, all other entries that were
+//! running are put back in the queue and resume from the files already on disk.
+//!
+//! # Events
+//!
+//! Emits a 'postcli-queue' event with a [`QueueSnapshot`] payload whenever the queue changes.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, State};
+
+use super::config::{PostCliConfig, PostCliError};
+use super::job_store::{self, JobStore};
+use super::jobs::{Job, JobExit, JobId, JobRegistry, JobStatus};
+use super::pos_data::PosLayout;
+use super::postcli;
+
+/// Name of the file holding the queue in the app data directory
+const QUEUE_FILE: &str = "postcli-queue.json";
+
+/// Identifier of a queue entry
+pub type EntryId = u64;
+
+/// State of a queue entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum EntryState {
+    /// Waiting to be started
+    Pending,
+    /// Claimed by the scheduler, PostCLI is being started
+    Starting,
+    /// Running as a PostCLI job
+    Running {
+        /// Registry id of the job in the current session
+        job_id: JobId,
+        /// Id of the persisted job record, stable across restarts
+        record_id: String,
+    },
+    /// PostCLI exited successfully
+    Completed,
+    /// PostCLI could not be started or exited with an error
+    Failed { message: String },
+}
+
+/// An initialization in the queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueEntry {
+    pub id: EntryId,
+    pub config: PostCliConfig,
+    #[serde(flatten)]
+    pub state: EntryState,
+}
+
+/// Persisted state of the queue, also used as the 'postcli-queue' event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueSnapshot {
+    pub entries: Vec<QueueEntry>,
+    /// Maximum number of jobs running at the same time on one provider
+    pub max_parallel_per_provider: u32,
+    #[serde(default)]
+    next_id: EntryId,
+}
+
+impl Default for QueueSnapshot {
+    fn default() -> Self {
+        QueueSnapshot {
+            entries: Vec::new(),
+            max_parallel_per_provider: 1,
+            next_id: 0,
+        }
+    }
+}
+
+impl QueueSnapshot {
+    /// Reads a persisted queue, an empty queue if the file is missing or unreadable
+    fn read(path: &Path) -> QueueSnapshot {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Resolves the entries that were running when the application stopped
+    ///
+    /// `find_job` returns the id and status of the job re-attached for a record id.
+    /// Entries that were being started are put back in the queue.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - Record ids of the jobs that did not survive
+    fn restore<F>(&mut self, find_job: F) -> Vec<String>
+    where
+        F: Fn(&str) -> Option<(JobId, JobStatus)>,
+    {
+        let mut lost = Vec::new();
+        for entry in self.entries.iter_mut() {
+            match &entry.state {
+                EntryState::Running { record_id, .. } => {
+                    let record_id = record_id.clone();
+                    entry.state = match find_job(&record_id) {
+                        Some((_, JobStatus::Exited { code, signal })) => exit_state(&entry.config, code, signal, None),
+                        Some((job_id, _)) => EntryState::Running { job_id, record_id },
+                        None => {
+                            lost.push(record_id);
+                            EntryState::Pending
+                        }
+                    };
+                }
+                EntryState::Starting => entry.state = EntryState::Pending,
+                _ => {}
+            }
+        }
+        lost
+    }
+
+    /// Marks the pending entries whose provider has free slots as starting
+    ///
+    /// Entries are considered in queue order; an entry whose provider is busy does
+    /// not block entries for other providers behind it.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(EntryId, PostCliConfig)>` - The claimed entries, in queue order
+    fn claim_startable(&mut self) -> Vec<(EntryId, PostCliConfig)> {
+        let mut running: HashMap<u32, u32> = HashMap::new();
+        for entry in self.entries.iter() {
+            if matches!(entry.state, EntryState::Running { .. } | EntryState::Starting) {
+                *running.entry(entry.config.provider).or_default() += 1;
+            }
+        }
+
+        let max_parallel = self.max_parallel_per_provider.max(1);
+        let mut claimed = Vec::new();
+        for entry in self.entries.iter_mut() {
+            if !matches!(entry.state, EntryState::Pending) {
+                continue;
+            }
+            let slots = running.entry(entry.config.provider).or_default();
+            if *slots < max_parallel {
+                *slots += 1;
+                entry.state = EntryState::Starting;
+                claimed.push((entry.id, entry.config.clone()));
+            }
+        }
+        claimed
+    }
+
+    /// Sets the state of a claimed entry once its job was spawned or failed to start
+    ///
+    /// A job that already exited is settled here, as [`on_job_exit`] did not find
+    /// it while the entry was starting.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the entry is running
+    fn started(&mut self, entry_id: EntryId, spawned: Result<Arc<Job>, String>) -> bool {
+        let entry = match self.entries.iter_mut().find(|e| e.id == entry_id) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        entry.state = match spawned {
+            Ok(job) => match job.status() {
+                JobStatus::Exited { code, signal } => {
+                    let report = job.exit_report();
+                    exit_state(&entry.config, code, signal, report.stderr_tail.last().map(String::as_str))
+                }
+                _ => EntryState::Running {
+                    job_id: job.id,
+                    record_id: job_store::record_id(&job),
+                },
+            },
+            Err(message) => EntryState::Failed { message },
+        };
+        matches!(entry.state, EntryState::Running { .. })
+    }
+
+    /// Completes or fails the entry of a job that exited
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the job belonged to an entry
+    fn finish_job(&mut self, exit: &JobExit) -> bool {
+        let entry = self.entries.iter_mut().find(|e| {
+            matches!(&e.state, EntryState::Running { job_id, .. } if *job_id == exit.job_id)
+        });
+        match entry {
+            Some(entry) => {
+                entry.state = exit_state(&entry.config, exit.code, exit.signal, exit.stderr_tail.last().map(String::as_str));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Tauri-managed initialization queue backed by a JSON file
+pub struct InitQueue {
+    path: Option<PathBuf>,
+    data: Mutex<QueueSnapshot>,
+}
+
+impl InitQueue {
+    /// Loads the queue from the application data directory
+    ///
+    /// Entries that were running when the application stopped are matched with the
+    /// jobs re-attached on startup, so the queue must be loaded after
+    /// [`job_store::recover_jobs`] and before their watchers report any exit.
+    /// Entries whose job already exited are completed or failed by its exit status.
+    /// Entries whose job did not survive, or that were still being started, are put
+    /// back in the queue; their interrupted job record is dropped, as the queue
+    /// takes care of resuming them.
+    pub fn load(app: &tauri::AppHandle) -> InitQueue {
+        let path = app.path_resolver().app_data_dir().map(|dir| dir.join(QUEUE_FILE));
+        let mut data = path.as_deref().map(QueueSnapshot::read).unwrap_or_default();
+
+        let registry = app.state::<JobRegistry>();
+        let store = app.state::<JobStore>();
+        let lost = data.restore(|record_id| {
+            registry
+                .find(|job| job_store::record_id(job) == record_id)
+                .map(|job| (job.id, job.status()))
+        });
+        for record_id in lost {
+            store.remove(&record_id);
+        }
+
+        InitQueue {
+            path,
+            data: Mutex::new(data),
+        }
+    }
+
+    /// Writes the queue to disk and announces the change
+    fn commit(&self, app: &tauri::AppHandle, data: &QueueSnapshot) {
+        if let Some(path) = &self.path {
+            let result = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| {
+                    let content = serde_json::to_string_pretty(data)
+                        .map_err(std::io::Error::other)?;
+                    fs::write(path, content)
+                });
+            if let Err(e) = result {
+                eprintln!("Failed to persist postcli queue to {:?}: {}", path, e);
+            }
+        }
+        if let Err(e) = app.emit_all("postcli-queue", data.clone()) {
+            eprintln!("Failed to emit postcli-queue event: {}", e);
+        }
+    }
+}
+
+/// Starts pending entries while their provider has free slots
+///
+/// The entries to start are claimed under the queue lock, but PostCLI is spawned
+/// without holding it. Entries that could not be started or whose job exited
+/// right away free their slot, so scheduling repeats until every claimed entry runs.
+pub fn schedule(app: &tauri::AppHandle) {
+    let queue = app.state::<InitQueue>();
+    let registry = app.state::<JobRegistry>();
+    let store = app.state::<JobStore>();
+
+    loop {
+        let claimed = queue.data.lock().unwrap().claim_startable();
+        if claimed.is_empty() {
+            return;
+        }
+
+        let spawned: Vec<_> = claimed
+            .into_iter()
+            .map(|(entry_id, config)| {
+                println!(
+                    "Starting queued initialization {} in {}",
+                    entry_id,
+                    config.datadir.as_deref().unwrap_or("the default directory")
+                );
+                (entry_id, postcli::spawn_detached(app.clone(), &registry, &store, config.to_args()))
+            })
+            .collect();
+
+        let mut data = queue.data.lock().unwrap();
+        let mut all_running = true;
+        for (entry_id, spawned) in spawned {
+            all_running &= data.started(entry_id, spawned);
+        }
+        queue.commit(app, &data);
+        if all_running {
+            return;
+        }
+    }
+}
+
+/// Updates the entry of a job that exited and starts the next entries
+///
+/// Called by the job watcher for every job; jobs not started by the queue are ignored.
+pub fn on_job_exit(app: &tauri::AppHandle, exit: &JobExit) {
+    let queue = match app.try_state::<InitQueue>() {
+        Some(queue) => queue,
+        None => return,
+    };
+    {
+        let mut data = queue.data.lock().unwrap();
+        if !data.finish_job(exit) {
+            return;
+        }
+        queue.commit(app, &data);
+    }
+    schedule(app);
+}
+
+/// State of an entry whose job exited with the given code or signal
+fn exit_state(config: &PostCliConfig, code: Option<i32>, signal: Option<i32>, last_stderr: Option<&str>) -> EntryState {
+    // Re-attached jobs report neither code nor signal, their data decides
+    let unknown_status = code.is_none() && signal.is_none();
+    if code == Some(0) || (unknown_status && data_complete(config)) {
+        return EntryState::Completed;
+    }
+    let reason = match (code, signal) {
+        (Some(code), _) => format!("postcli exited with code {}", code),
+        (None, Some(signal)) => format!("postcli was killed by signal {}", signal),
+        (None, None) => "postcli exited with an unknown status".to_string(),
+    };
+    let message = match last_stderr {
+        Some(line) => format!("{}: {}", reason, line),
+        None => reason,
+    };
+    EntryState::Failed { message }
+}
+
+/// Adds an initialization to the end of the queue
+///
+/// # Arguments
+///
+/// * `config` - Full PostCLI configuration of the initialization
+///
+/// # Returns
+///
+/// * `Ok(QueueEntry)` - The new entry, which is started right away if its provider is idle
+/// * `Err(PostCliError)` - If the configuration is invalid
+#[tauri::command]
+pub fn queue_add(
+    app: tauri::AppHandle,
+    queue: State<'_, InitQueue>,
+    config: PostCliConfig,
+) -> Result<QueueEntry, PostCliError> {
+    config.validate_init()?;
+    let entry = {
+        let mut data = queue.data.lock().unwrap();
+        data.next_id = data.next_id.max(data.entries.iter().map(|e| e.id).max().unwrap_or(0)) + 1;
+        let entry = QueueEntry {
+            id: data.next_id,
+            config,
+            state: EntryState::Pending,
+        };
+        data.entries.push(entry.clone());
+        queue.commit(&app, &data);
+        entry
+    };
+    schedule(&app);
+    Ok(entry)
+}
+
+/// Removes an entry that is not running from the queue
+///
+/// # Arguments
+///
+/// * `entry_id` - Id of the entry to remove
+///
+/// # Returns
+///
+/// * `Ok(())` - If the entry was removed
+/// * `Err(String)` - If the entry is unknown or currently running
+#[tauri::command]
+pub fn queue_remove(app: tauri::AppHandle, queue: State<'_, InitQueue>, entry_id: EntryId) -> Result<(), String> {
+    let mut data = queue.data.lock().unwrap();
+    let index = find_entry(&data, entry_id)?;
+    match data.entries[index].state {
+        EntryState::Running { job_id, .. } => {
+            return Err(format!("Entry {} is running as job {}, stop it first", entry_id, job_id))
+        }
+        EntryState::Starting => return Err(format!("Entry {} is being started", entry_id)),
+        _ => {}
+    }
+    data.entries.remove(index);
+    queue.commit(&app, &data);
+    Ok(())
+}
+
+/// Moves an entry to another position in the queue
+///
+/// # Arguments
+///
+/// * `entry_id` - Id of the entry to move
+/// * `position` - New zero-based position, clamped to the end of the queue
+#[tauri::command]
+pub fn queue_reorder(
+    app: tauri::AppHandle,
+    queue: State<'_, InitQueue>,
+    entry_id: EntryId,
+    position: usize,
+) -> Result<(), String> {
+    {
+        let mut data = queue.data.lock().unwrap();
+        let index = find_entry(&data, entry_id)?;
+        let entry = data.entries.remove(index);
+        let position = position.min(data.entries.len());
+        data.entries.insert(position, entry);
+        queue.commit(&app, &data);
+    }
+    schedule(&app);
+    Ok(())
+}
+
+/// Returns the current queue
+#[tauri::command]
+pub fn queue_list(queue: State<'_, InitQueue>) -> QueueSnapshot {
+    queue.data.lock().unwrap().clone()
+}
+
+/// Sets how many queued jobs may run at the same time on one provider
+///
+/// # Arguments
+///
+/// * `max_parallel_per_provider` - Maximum number of parallel jobs per provider (at least 1)
+#[tauri::command]
+pub fn queue_set_parallelism(
+    app: tauri::AppHandle,
+    queue: State<'_, InitQueue>,
+    max_parallel_per_provider: u32,
+) -> Result<(), String> {
+    if max_parallel_per_provider == 0 {
+        return Err("At least one job per provider must be allowed".to_string());
+    }
+    {
+        let mut data = queue.data.lock().unwrap();
+        data.max_parallel_per_provider = max_parallel_per_provider;
+        queue.commit(&app, &data);
+    }
+    schedule(&app);
+    Ok(())
+}
+
+/// Checks whether all data files of an initialization are present and complete
+///
+/// Used for re-attached jobs, whose exit code cannot be retrieved.
+fn data_complete(config: &PostCliConfig) -> bool {
+    let layout = PosLayout::from_args(&config.to_args());
+    layout.num_files().checked_sub(1).is_some_and(|last| layout.last_complete_file() == Some(last))
+}
+
+/// Finds the position of an entry in the queue
+fn find_entry(data: &QueueSnapshot, entry_id: EntryId) -> Result<usize, String> {
+    data.entries
+        .iter()
+        .position(|e| e.id == entry_id)
+        .ok_or_else(|| format!("Unknown queue entry {}", entry_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{data_dir, temp_dir};
+
+    fn entry(id: EntryId, provider: u32, state: EntryState) -> QueueEntry {
+        QueueEntry {
+            id,
+            config: PostCliConfig {
+                provider,
+                ..PostCliConfig::default()
+            },
+            state,
+        }
+    }
+
+    fn running(job_id: JobId, record_id: &str) -> EntryState {
+        EntryState::Running {
+            job_id,
+            record_id: record_id.to_string(),
+        }
+    }
+
+    fn states(data: &QueueSnapshot) -> Vec<&'static str> {
+        data.entries
+            .iter()
+            .map(|e| match e.state {
+                EntryState::Pending => "pending",
+                EntryState::Starting => "starting",
+                EntryState::Running { .. } => "running",
+                EntryState::Completed => "completed",
+                EntryState::Failed { .. } => "failed",
+            })
+            .collect()
+    }
+
+    /// Configuration of two data files of 1 KiB in a directory
+    fn small_config(datadir: &Path) -> PostCliConfig {
+        PostCliConfig {
+            datadir: Some(datadir.to_string_lossy().to_string()),
+            num_units: 2,
+            labels_per_unit: Some(64),
+            max_file_size: Some(1024),
+            ..PostCliConfig::default()
+        }
+    }
+
+    #[test]
+    fn claims_free_slots_per_provider() {
+        let mut data = QueueSnapshot {
+            entries: vec![
+                entry(1, 0, running(10, "a")),
+                entry(2, 0, EntryState::Pending),
+                entry(3, 1, EntryState::Pending),
+                entry(4, 1, EntryState::Pending),
+                entry(5, 0, EntryState::Completed),
+            ],
+            ..QueueSnapshot::default()
+        };
+        let ids = |claimed: Vec<(EntryId, PostCliConfig)>| claimed.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+
+        assert_eq!(ids(data.claim_startable()), vec![3]);
+        assert_eq!(states(&data), vec!["running", "pending", "starting", "pending", "completed"]);
+        // Entries being started occupy their slot
+        assert!(data.claim_startable().is_empty());
+
+        data.max_parallel_per_provider = 2;
+        assert_eq!(ids(data.claim_startable()), vec![2, 4]);
+        assert!(data.claim_startable().is_empty());
+    }
+
+    #[test]
+    fn reloads_queue_and_restores_running_entries() {
+        let path = temp_dir("queue-reload").join(QUEUE_FILE);
+        let data = QueueSnapshot {
+            entries: vec![
+                entry(1, 0, running(1, "alive")),
+                entry(2, 0, running(2, "exited")),
+                entry(3, 1, running(3, "lost")),
+                entry(4, 1, EntryState::Starting),
+                entry(5, 1, EntryState::Failed { message: "failed".to_string() }),
+            ],
+            max_parallel_per_provider: 2,
+            next_id: 5,
+        };
+        fs::write(&path, serde_json::to_string(&data).unwrap()).unwrap();
+
+        let mut data = QueueSnapshot::read(&path);
+        assert_eq!(data.max_parallel_per_provider, 2);
+        assert_eq!(data.next_id, 5);
+        let lost = data.restore(|record_id| match record_id {
+            "alive" => Some((7, JobStatus::Running)),
+            "exited" => Some((8, JobStatus::Exited { code: Some(0), signal: None })),
+            _ => None,
+        });
+        assert_eq!(lost, vec!["lost"]);
+        assert_eq!(states(&data), vec!["running", "completed", "pending", "pending", "failed"]);
+        assert!(matches!(&data.entries[0].state, EntryState::Running { job_id: 7, record_id } if record_id == "alive"));
+
+        assert_eq!(QueueSnapshot::read(&path.with_extension("missing")).entries.len(), 0);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn settles_entries_of_exited_jobs() {
+        let mut data = QueueSnapshot {
+            entries: vec![entry(1, 0, running(10, "a")), entry(2, 0, running(11, "b"))],
+            ..QueueSnapshot::default()
+        };
+        let exit = |job_id: JobId, code: i32| JobExit {
+            job_id,
+            process_id: 1,
+            code: Some(code),
+            signal: None,
+            runtime_secs: 1.0,
+            stderr_tail: vec!["first".to_string(), "no space left".to_string()],
+        };
+
+        assert!(data.finish_job(&exit(10, 0)));
+        assert!(data.finish_job(&exit(11, 1)));
+        assert!(!data.finish_job(&exit(12, 0)));
+        assert_eq!(states(&data), vec!["completed", "failed"]);
+        assert!(
+            matches!(&data.entries[1].state, EntryState::Failed { message } if message == "postcli exited with code 1: no space left")
+        );
+    }
+
+    #[test]
+    fn settles_claimed_entries_after_spawning() {
+        let dir = data_dir("queue-started", &[1024, 512]);
+        let mut data = QueueSnapshot {
+            entries: vec![entry(1, 0, EntryState::Starting), entry(2, 0, EntryState::Starting)],
+            ..QueueSnapshot::default()
+        };
+        data.entries[1].config = small_config(&dir);
+
+        let registry = JobRegistry::default();
+        let job = registry.attach(1, Vec::new(), 0);
+        assert!(data.started(1, Ok(job.clone())));
+        assert!(matches!(data.entries[0].state, EntryState::Running { job_id, .. } if job_id == job.id));
+
+        // A job that exited before its entry was running is settled from its data
+        let exited = registry.attach(2, Vec::new(), 0);
+        exited.finish(None);
+        assert!(!data.started(2, Ok(exited)));
+        assert!(!data.started(3, Err("unknown entry".to_string())));
+        assert_eq!(states(&data), vec!["running", "failed"]);
+
+        data.entries[0].state = EntryState::Starting;
+        assert!(!data.started(1, Err("postcli not found".to_string())));
+        assert!(matches!(&data.entries[0].state, EntryState::Failed { message } if message == "postcli not found"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn checks_data_completeness() {
+        let dir = data_dir("queue-complete", &[1024, 512]);
+        assert!(!data_complete(&small_config(&dir)));
+        fs::write(dir.join("postdata_1.bin"), vec![0u8; 1024]).unwrap();
+        assert!(data_complete(&small_config(&dir)));
+
+        let empty = PostCliConfig {
+            num_units: 0,
+            ..small_config(&dir)
+        };
+        assert!(!data_complete(&empty));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,449 @@
+//! Split Initialization Module
+//!
+//! This module spreads a single initialization over several OpenCL providers.
+//! The file index range of the data directory is partitioned among the providers
+//! in proportion to their measured speed, and one PostCLI process is launched per
+//! slice with `-fromFile`/`-toFile`. Once every slice has completed, a final PostCLI
+//! run over the whole directory skips the existing files and writes the metadata
+//! including the VRF nonce.
+//!
+//! A coordinator thread aggregates the progress of all slices into a single view.
+//! Each slice is also a regular job with its own 'postcli-*' events, so it can be
+//! paused or stopped individually. If a slice fails, the other slices are stopped.
+//! The coordinator keeps the slice jobs, so their exit status is still known after
+//! the job registry pruned them.
+//!
+//! The identity must be given, so that the slices do not each create their own
+//! identity key in the data directory.
+//!
+//! # Events
+//!
+//! Emits a 'postcli-split-progress' event with a [`SplitProgress`] payload on every
+//! scan and whenever the state of the split changes.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, State};
+
+use super::config::{FieldError, PostCliConfig, PostCliError};
+use super::job_store::JobStore;
+use super::jobs::{Job, JobId, JobRegistry, JobStatus};
+use super::monitor;
+use super::pos_data::PosLayout;
+use super::postcli;
+
+/// Identifier of a split initialization
+pub type SplitId = u64;
+
+/// Provider taking part in a split initialization
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderSpeed {
+    /// Index of the OpenCL provider
+    pub provider: u32,
+    /// Measured speed in any unit shared by all providers (e.g. labels per second);
+    /// providers without a measurement get an equal share
+    pub speed: Option<f64>,
+}
+
+/// Contiguous range of files assigned to one provider
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileSlice {
+    pub provider: u32,
+    /// First file of the slice
+    pub from_file: u32,
+    /// Last file of the slice, inclusive
+    pub to_file: u32,
+}
+
+/// State of a split initialization
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SplitState {
+    /// The slices are being initialized
+    Initializing,
+    /// All slices are complete, the final run writes the metadata and nonce
+    Finalizing { job_id: JobId },
+    Completed,
+    Failed { message: String },
+}
+
+/// Progress of one slice of a split initialization
+#[derive(Debug, Clone, Serialize)]
+pub struct SliceProgress {
+    #[serde(flatten)]
+    pub slice: FileSlice,
+    pub job_id: JobId,
+    pub status: JobStatus,
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+}
+
+/// Aggregated view of a split initialization, payload of the 'postcli-split-progress' event
+#[derive(Debug, Clone, Serialize)]
+pub struct SplitProgress {
+    pub split_id: SplitId,
+    pub datadir: String,
+    #[serde(flatten)]
+    pub state: SplitState,
+    /// Bytes of PoS data present in the data directory
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+    /// Completion percentage (0-100)
+    pub percentage: f64,
+    /// Combined write rate of all slices since the split started in bytes per second
+    pub average_rate: f64,
+    /// Estimated time remaining in seconds until all slices are complete
+    pub eta_secs: Option<f64>,
+    pub slices: Vec<SliceProgress>,
+}
+
+/// Tauri-managed registry of the split initializations started during this session
+#[derive(Default)]
+pub struct SplitRegistry {
+    next_id: AtomicU64,
+    splits: Mutex<HashMap<SplitId, SplitProgress>>,
+}
+
+impl SplitRegistry {
+    /// Stores the latest view of a split and emits it to the frontend
+    fn update(&self, app: &tauri::AppHandle, progress: SplitProgress) {
+        self.splits.lock().unwrap().insert(progress.split_id, progress.clone());
+        if let Err(e) = app.emit_all("postcli-split-progress", progress) {
+            eprintln!("Failed to emit postcli-split-progress event: {}", e);
+        }
+    }
+}
+
+/// Partitions the files of an initialization among providers by speed
+///
+/// Every provider gets a contiguous range of files, in the order the providers
+/// are given, sized in proportion to its speed. Rounding uses the largest
+/// remainder method, so the slices always cover all files exactly once.
+/// Providers whose share rounds to zero files get no slice.
+///
+/// # Arguments
+///
+/// * `total_files` - Number of files of the initialization
+/// * `providers` - Providers with their measured speeds
+pub fn partition_files(total_files: u32, providers: &[ProviderSpeed]) -> Vec<FileSlice> {
+    let measured = providers.iter().all(|p| p.speed.is_some_and(|s| s > 0.0));
+    let weights: Vec<f64> = providers
+        .iter()
+        .map(|p| if measured { p.speed.unwrap_or(0.0) } else { 1.0 })
+        .collect();
+    let total_weight: f64 = weights.iter().sum();
+    if total_files == 0 || total_weight <= 0.0 {
+        return Vec::new();
+    }
+
+    let exact: Vec<f64> = weights
+        .iter()
+        .map(|w| w / total_weight * total_files as f64)
+        .collect();
+    let mut counts: Vec<u32> = exact.iter().map(|e| e.floor() as u32).collect();
+    let mut by_remainder: Vec<usize> = (0..exact.len()).collect();
+    by_remainder.sort_by(|a, b| {
+        let (ra, rb) = (exact[*a] - exact[*a].floor(), exact[*b] - exact[*b].floor());
+        rb.partial_cmp(&ra).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let assigned: u32 = counts.iter().sum();
+    for index in by_remainder.into_iter().take((total_files - assigned) as usize) {
+        counts[index] += 1;
+    }
+
+    let mut slices = Vec::new();
+    let mut next_file = 0;
+    for (provider, count) in providers.iter().zip(counts) {
+        if count == 0 {
+            continue;
+        }
+        slices.push(FileSlice {
+            provider: provider.provider,
+            from_file: next_file,
+            to_file: next_file + count - 1,
+        });
+        next_file += count;
+    }
+    slices
+}
+
+/// Starts an initialization split across several providers
+///
+/// # Arguments
+///
+/// * `config` - PostCLI configuration of the whole initialization; its `provider` is ignored
+///   and its `id` is required
+/// * `providers` - Providers to use with their measured speeds
+///
+/// # Returns
+///
+/// * `Ok(SplitProgress)` - Initial view of the split
+/// * `Err(PostCliError)` - If the configuration or providers are invalid or a slice
+///   could not be started; slices that were already started are stopped again
+///
+/// # Events
+///
+/// Emits 'postcli-split-progress' events until the split completes or fails.
+#[tauri::command]
+pub async fn run_postcli_split(
+    config: PostCliConfig,
+    providers: Vec<ProviderSpeed>,
+    app: tauri::AppHandle,
+    registry: State<'_, JobRegistry>,
+    store: State<'_, JobStore>,
+    splits: State<'_, SplitRegistry>,
+) -> Result<SplitProgress, PostCliError> {
+    let config = PostCliConfig {
+        from_file: None,
+        to_file: None,
+        ..config
+    };
+    config.validate_init()?;
+    if config.id.is_none() {
+        return Err(PostCliError::Validation {
+            errors: vec![FieldError {
+                field: "id",
+                message: "is required to split an initialization, so that all slices share the identity"
+                    .to_string(),
+            }],
+        });
+    }
+    if providers.is_empty() {
+        return Err("At least one provider is required".into());
+    }
+    for (i, p) in providers.iter().enumerate() {
+        if providers[..i].iter().any(|other| other.provider == p.provider) {
+            return Err(format!("Provider {} is listed more than once", p.provider).into());
+        }
+    }
+
+    let layout = PosLayout::from_args(&config.to_args());
+    let slices = partition_files(layout.num_files(), &providers);
+    if slices.is_empty() {
+        return Err("The initialization has no files to split".into());
+    }
+
+    let mut started = Vec::new();
+    for slice in &slices {
+        // The proof is only generated by the final run over the whole directory
+        let slice_config = PostCliConfig {
+            provider: slice.provider,
+            from_file: Some(slice.from_file),
+            to_file: Some(slice.to_file),
+            genproof: false,
+            ..config.clone()
+        };
+
+        match postcli::spawn_detached(app.clone(), registry.inner(), store.inner(), slice_config.to_args()) {
+            Ok(job) => started.push((slice.clone(), job)),
+            Err(e) => {
+                stop_slices(started.iter().map(|(_, job)| job));
+                return Err(format!(
+                    "Failed to start slice {}-{} on provider {}: {}",
+                    slice.from_file, slice.to_file, slice.provider, e
+                )
+                .into());
+            }
+        }
+    }
+
+    let split_id = splits.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+    println!("Started split initialization {} with slices {:?}", split_id, slices);
+    let progress = SplitProgress {
+        split_id,
+        datadir: layout.datadir.to_string_lossy().to_string(),
+        state: SplitState::Initializing,
+        bytes_done: 0,
+        total_bytes: layout.total_bytes(),
+        percentage: 0.0,
+        average_rate: 0.0,
+        eta_secs: None,
+        slices: Vec::new(),
+    };
+    splits.update(&app, progress.clone());
+    coordinate_split(app, split_id, config, layout, started);
+    Ok(progress)
+}
+
+/// Lists the split initializations started during this session
+#[tauri::command]
+pub fn list_postcli_splits(splits: State<'_, SplitRegistry>) -> Vec<SplitProgress> {
+    let mut list: Vec<SplitProgress> = splits.splits.lock().unwrap().values().cloned().collect();
+    list.sort_by_key(|s| s.split_id);
+    list
+}
+
+/// Stops the slice jobs that are still running in the background
+fn stop_slices<'a, I: IntoIterator<Item = &'a Arc<Job>>>(jobs: I) {
+    for job in jobs.into_iter().filter(|job| job.is_alive()) {
+        let job = job.clone();
+        thread::spawn(move || {
+            let grace_period = Duration::from_secs(postcli::DEFAULT_STOP_GRACE_PERIOD_SECS);
+            if let Err(e) = postcli::stop_job(&job, grace_period) {
+                eprintln!("Failed to stop slice job {}: {}", job.id, e);
+            }
+        });
+    }
+}
+
+/// Aggregates the progress of the slices and runs the final step in the background
+fn coordinate_split(
+    app: tauri::AppHandle,
+    split_id: SplitId,
+    config: PostCliConfig,
+    layout: PosLayout,
+    slices: Vec<(FileSlice, Arc<Job>)>,
+) {
+    thread::spawn(move || {
+        let registry = app.state::<JobRegistry>();
+        let splits = app.state::<SplitRegistry>();
+        let started = Instant::now();
+        let (initial_bytes, _) = monitor::scan_progress(&layout);
+        let mut state = SplitState::Initializing;
+        let mut final_job: Option<Arc<Job>> = None;
+
+        loop {
+            thread::sleep(monitor::MONITOR_INTERVAL);
+
+            let mut views = Vec::new();
+            for (slice, job) in &slices {
+                let mut slice_layout = layout.clone();
+                slice_layout.from_file = Some(slice.from_file);
+                slice_layout.to_file = Some(slice.to_file);
+                let (bytes_done, _) = monitor::scan_progress(&slice_layout);
+                views.push(SliceProgress {
+                    slice: slice.clone(),
+                    job_id: job.id,
+                    status: job.status(),
+                    bytes_done,
+                    total_bytes: slice_layout.range_bytes(),
+                });
+            }
+
+            state = match state {
+                SplitState::Initializing => {
+                    let failed = views.iter().find(|v| match v.status {
+                        JobStatus::Exited { code, .. } => code != Some(0),
+                        _ => false,
+                    });
+                    let done = views
+                        .iter()
+                        .all(|v| matches!(v.status, JobStatus::Exited { code: Some(0), .. }));
+                    if let Some(view) = failed {
+                        stop_slices(slices.iter().map(|(_, job)| job));
+                        SplitState::Failed {
+                            message: format!(
+                                "Slice {}-{} on provider {} failed",
+                                view.slice.from_file, view.slice.to_file, view.slice.provider
+                            ),
+                        }
+                    } else if done {
+                        println!("All slices of split {} completed, writing metadata", split_id);
+                        let store = app.state::<JobStore>();
+                        match postcli::spawn_detached(app.clone(), &registry, &store, config.to_args()) {
+                            Ok(job) => {
+                                let job_id = job.id;
+                                final_job = Some(job);
+                                SplitState::Finalizing { job_id }
+                            }
+                            Err(message) => SplitState::Failed { message },
+                        }
+                    } else {
+                        SplitState::Initializing
+                    }
+                }
+                SplitState::Finalizing { job_id } => match final_job.as_ref().map(|job| job.status()) {
+                    Some(JobStatus::Exited { code: Some(0), .. }) => SplitState::Completed,
+                    Some(JobStatus::Exited { code, signal }) => SplitState::Failed {
+                        message: format!("Final postcli run exited (code: {:?}, signal: {:?})", code, signal),
+                    },
+                    _ => SplitState::Finalizing { job_id },
+                },
+                finished => finished,
+            };
+
+            let (bytes_done, _) = monitor::scan_progress(&layout);
+            let total_bytes = layout.total_bytes();
+            let elapsed = started.elapsed().as_secs_f64();
+            let average_rate = if elapsed > 0.0 {
+                bytes_done.saturating_sub(initial_bytes) as f64 / elapsed
+            } else {
+                0.0
+            };
+            let finished = matches!(state, SplitState::Completed | SplitState::Failed { .. });
+            splits.update(
+                &app,
+                SplitProgress {
+                    split_id,
+                    datadir: layout.datadir.to_string_lossy().to_string(),
+                    state: state.clone(),
+                    bytes_done,
+                    total_bytes,
+                    percentage: if total_bytes > 0 {
+                        bytes_done as f64 / total_bytes as f64 * 100.0
+                    } else {
+                        0.0
+                    },
+                    average_rate,
+                    eta_secs: (average_rate > 0.0)
+                        .then(|| total_bytes.saturating_sub(bytes_done) as f64 / average_rate),
+                    slices: views,
+                },
+            );
+
+            if finished {
+                println!("Split initialization {} finished: {:?}", split_id, state);
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(provider: u32, speed: Option<f64>) -> ProviderSpeed {
+        ProviderSpeed { provider, speed }
+    }
+
+    fn slice(provider: u32, from_file: u32, to_file: u32) -> FileSlice {
+        FileSlice { provider, from_file, to_file }
+    }
+
+    #[test]
+    fn splits_evenly_without_measurements() {
+        let slices = partition_files(10, &[provider(0, None), provider(1, None)]);
+        assert_eq!(slices, vec![slice(0, 0, 4), slice(1, 5, 9)]);
+    }
+
+    #[test]
+    fn splits_by_speed() {
+        let slices = partition_files(16, &[provider(2, Some(300.0)), provider(0, Some(100.0))]);
+        assert_eq!(slices, vec![slice(2, 0, 11), slice(0, 12, 15)]);
+    }
+
+    #[test]
+    fn assigns_leftover_files_by_largest_remainder() {
+        // Exact shares are 3.33, 3.33 and 3.33 of 10, the first provider gets the extra file
+        let providers = [provider(0, Some(1.0)), provider(1, Some(1.0)), provider(2, Some(1.0))];
+        let slices = partition_files(10, &providers);
+        assert_eq!(slices, vec![slice(0, 0, 3), slice(1, 4, 6), slice(2, 7, 9)]);
+    }
+
+    #[test]
+    fn skips_providers_without_files() {
+        let slices = partition_files(2, &[provider(0, Some(100.0)), provider(1, Some(1.0))]);
+        assert_eq!(slices, vec![slice(0, 0, 1)]);
+    }
+
+    #[test]
+    fn falls_back_to_equal_shares_with_partial_measurements() {
+        let slices = partition_files(4, &[provider(0, Some(100.0)), provider(1, None)]);
+        assert_eq!(slices, vec![slice(0, 0, 1), slice(1, 2, 3)]);
+    }
+}
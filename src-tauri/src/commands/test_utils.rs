@@ -0,0 +1,26 @@
+//! Test Utilities Module
+//!
+//! Fixtures shared by the unit tests of the command modules.
+
+use std::fs;
+use std::path::PathBuf;
+
+/// Creates an empty temporary directory
+///
+/// The directory is named after the test and the process, so tests running in
+/// parallel do not share it. A directory left over by a previous run is replaced.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sm-init-test-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Creates a temporary directory holding `postdata_N.bin` files of the given sizes, see [`temp_dir`]
+pub fn data_dir(name: &str, sizes: &[usize]) -> PathBuf {
+    let dir = temp_dir(name);
+    for (index, size) in sizes.iter().enumerate() {
+        fs::write(dir.join(format!("postdata_{}.bin", index)), vec![0u8; *size]).unwrap();
+    }
+    dir
+}
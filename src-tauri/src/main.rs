@@ -6,20 +6,76 @@
 
 mod commands;
 
+use tauri::Manager;
+
 fn main() {
     tauri::Builder::default()
+        .manage(commands::jobs::JobRegistry::default())
+        .manage(commands::split::SplitRegistry::default())
+        .manage(commands::postcli::DeveloperMode::from_env())
+        .manage(commands::calls::CallRegistry::default())
+        .manage(commands::profiler::ProfilerRuns::default())
+        .setup(|app| {
+            let handle = app.handle();
+            app.manage(commands::job_store::JobStore::load(&handle));
+            app.manage(commands::job_logs::JobLogs::new(&handle));
+            app.manage(commands::profiler_history::ProfilerHistory::load(&handle));
+            let recovered = commands::job_store::recover_jobs(&handle);
+            app.manage(commands::queue::InitQueue::load(&handle));
+            commands::job_store::watch_recovered_jobs(&handle, recovered);
+            commands::queue::schedule(&handle);
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::file_dialog::select_directory,
             commands::file_dialog::verify_directory,
             commands::file_dialog::check_directory_space,
             commands::file_dialog::check_write_permission,
+            commands::file_dialog::get_filesystem_info,
+            commands::disk_benchmark::benchmark_directory_write,
+            commands::disk_benchmark::benchmark_directory_read,
             commands::postcli::run_postcli_command,
             commands::postcli::run_postcli_detached,
+            commands::postcli::run_postcli_genproof,
+            commands::postcli::print_postcli_providers,
+            commands::postcli::get_postcli_version,
+            commands::postcli::get_postcli_help,
+            commands::postcli::is_developer_mode,
+            commands::calls::cancel_postcli_call,
+            commands::job_logs::list_job_logs,
+            commands::job_logs::tail_job_log,
+            commands::job_logs::search_job_log,
+            commands::pos_metadata::read_pos_metadata,
+            commands::pos_inspect::inspect_pos_directory,
             commands::postcli::stop_postcli_process,
+            commands::postcli::list_postcli_jobs,
+            commands::postcli::pause_postcli_job,
+            commands::postcli::resume_postcli_job,
+            commands::postcli::list_interrupted_jobs,
+            commands::postcli::resume_interrupted_job,
+            commands::postcli::discard_interrupted_job,
+            commands::queue::queue_add,
+            commands::queue::queue_remove,
+            commands::queue::queue_reorder,
+            commands::queue::queue_list,
+            commands::queue::queue_set_parallelism,
+            commands::split::run_postcli_split,
+            commands::split::list_postcli_splits,
             commands::cpu::get_cpu_cores,
             commands::profiler::run_profiler,
+            commands::profiler::run_profiler_sweep,
+            commands::profiler::run_profiler_on_pos_data,
+            commands::profiler::cancel_profiler,
             commands::profiler::get_default_config,
             commands::profiler::calculate_post_probability,
+            commands::proving::calculate_proving_probability,
+            commands::proving::estimate_proving_feasibility,
+            commands::optimizer::optimize_proving_config,
+            commands::profiler_history::list_profiler_history,
+            commands::profiler_history::delete_profiler_history,
+            commands::profiler_history::export_profiler_history,
+            commands::profiler_history::get_hardware_fingerprint,
+            commands::profiler_history::get_cached_profiler_result,
             commands::fs::get_file_size,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,96 @@
+import { useEffect, useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import styled from 'styled-components';
+
+import {
+  discardInterruptedJob,
+  InterruptedJob,
+  listInterruptedJobs,
+  resumeInterruptedJob,
+} from '../services/postcliService';
+import { usePOSProcess } from '../state/POSProcessContext';
+import { ErrorMessage } from '../styles/texts';
+
+import { Button } from './button';
+import Modal from './modal';
+
+const ButtonWrapper = styled.div`
+  display: flex;
+  justify-content: space-between;
+  margin-top: 40px;
+  flex-direction: row;
+  gap: 20px;
+`;
+
+/**
+ * Offers to resume the postcli jobs interrupted by the app closing or crashing
+ * Jobs are shown one after another until each is resumed or discarded
+ */
+export const InterruptedJobsModal = () => {
+  const navigate = useNavigate();
+  const { startProcess, processState } = usePOSProcess();
+  const [jobs, setJobs] = useState<InterruptedJob[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listInterruptedJobs()
+      .then(setJobs)
+      .catch((err) => console.error('Failed to list interrupted jobs:', err));
+  }, []);
+
+  const job = jobs[0];
+  if (!job || processState.isRunning) {
+    return null;
+  }
+
+  const next = () => {
+    setError(null);
+    setJobs((prev) => prev.slice(1));
+  };
+
+  const handleResume = async () => {
+    try {
+      const response = await resumeInterruptedJob(job.id);
+      startProcess(response.job_id);
+      next();
+      navigate('/progress');
+    } catch (err) {
+      setError(`Failed to resume POS generation: ${err}`);
+    }
+  };
+
+  const handleDiscard = async () => {
+    try {
+      await discardInterruptedJob(job.id);
+      next();
+    } catch (err) {
+      setError(`Failed to discard POS generation: ${err}`);
+    }
+  };
+
+  const progress = job.last_progress
+    ? ` It was ${job.last_progress.percentage.toFixed(1)}% done.`
+    : '';
+
+  return (
+    <Modal
+      isOpen
+      onClose={() => setJobs([])}
+      header="POS Generation Interrupted"
+      text={
+        <>
+          The POS generation in {job.datadir} stopped while the app was closed.
+          {progress} Do you want to continue it?
+          {error && <ErrorMessage>{error}</ErrorMessage>}
+        </>
+      }
+      width={600}
+      height={300}
+    >
+      <ButtonWrapper>
+        <Button label="Resume generation" onClick={handleResume} width={200} />
+        <Button label="Discard" onClick={handleDiscard} width={200} />
+      </ButtonWrapper>
+    </Modal>
+  );
+};
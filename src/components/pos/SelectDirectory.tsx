@@ -34,6 +34,7 @@ const CompactWrapper = styled(SetupTileWrapper)`
  * - Native directory picker integration
  * - Directory validation
  * - Error handling
+ * - Filesystem warnings (FAT32, network shares, removable drives)
  * - Path display formatting
  * - Loading state management
  *
@@ -46,7 +47,7 @@ export const SelectDirectory: React.FC<SelectDirectoryProps> = ({
   showExplanation = false,
 }) => {
   const { settings } = useSettings();
-  const { selectDirectory, error, isValidating } = usePosDirectory();
+  const { selectDirectory, error, warnings, isValidating } = usePosDirectory();
 
   // Display either selected directory or default path
   const displayPath =
@@ -59,7 +60,7 @@ export const SelectDirectory: React.FC<SelectDirectoryProps> = ({
       <Tile
         heading="Select where to store POS data"
         footer={variant === 'compact' ? shortenPath(displayPath, 30) : ''}
-        errmsg={error ?? undefined}
+        errmsg={error ?? (warnings.length > 0 ? warnings.join('. ') : undefined)}
         height={250}
       />
       <Button
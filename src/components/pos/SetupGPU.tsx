@@ -81,7 +81,7 @@ export const SetupGPU: React.FC<Props> = ({ isOpen, initialProviders }) => {
 
     const detectProviders = async () => {
       if (!mountedRef.current) return;
-      await run(["-printProviders"], updateConsole);
+      await run(updateConsole);
     };
 
     if (isOpen && !initialProviders) {
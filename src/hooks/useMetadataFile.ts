@@ -1,11 +1,9 @@
-import { readTextFile } from '@tauri-apps/api/fs';
-import { join } from '@tauri-apps/api/path';
+import { invoke } from '@tauri-apps/api/tauri';
 import { useEffect, useState } from 'react';
 
 import { SizeConstants } from '../Shared/Constants';
 import { useSettings } from '../state/SettingsContext';
-import { ParsedMetadata, PosMetadata } from '../types/metadata';
-import { base64ToHex } from '../utils/hexUtils';
+import { ParsedMetadata, PosMetadataReport } from '../types/metadata';
 
 import { usePosDirectory } from './usePosDirectory';
 
@@ -26,20 +24,22 @@ export const useMetadataFile = () => {
       (async () => {
         // Check for metadata file to load settings if exist
         try {
-          const metadataPath = await join(dir, 'postdata_metadata.json');
-          const metadataContent = await readTextFile(metadataPath);
-          const parsedMetadata: PosMetadata = JSON.parse(metadataContent);
+          const report = await invoke<PosMetadataReport>('read_pos_metadata', {
+            dir,
+            labelsPerUnit: SizeConstants.DEFAULT_LABELS_PER_UNIT,
+          });
           const metadata: ParsedMetadata = {
-            atxId: base64ToHex(parsedMetadata.CommitmentAtxId).toLowerCase(),
-            publicKey: base64ToHex(parsedMetadata.NodeId).toLowerCase(),
-            numUnits: parsedMetadata.NumUnits,
-            maxFileSize: parsedMetadata.MaxFileSize / 1024 / 1024,
+            atxId: report.commitment_atx_id,
+            publicKey: report.node_id,
+            numUnits: report.num_units,
+            maxFileSize: report.max_file_size / 1024 / 1024,
           };
 
-          // Validate labelsPerUnit
-          if (parsedMetadata.LabelsPerUnit !== SizeConstants.DEFAULT_LABELS_PER_UNIT) {
+          // An unfinished initialization can be resumed, any other problem cannot
+          const problems = report.problems.filter((problem) => problem.kind !== 'init_not_finished');
+          if (problems.length > 0) {
             setError(
-              `LabelsPerUnit mismatch, got ${parsedMetadata.LabelsPerUnit}, expected ${SizeConstants.DEFAULT_LABELS_PER_UNIT}. Please select another directory.`
+              `${problems.map((problem) => problem.message).join('. ')}. Please select another directory.`
             );
             setSettings((prev) => ({ ...prev, selectedDir: undefined }));
             setIsOpenModal(true);
@@ -55,8 +55,8 @@ export const useMetadataFile = () => {
 
           setMetadata(metadata);
           setIsOpenModal(true);
-        } catch {
-          console.log('No metadata file found');
+        } catch (err) {
+          console.log('No metadata file found:', err);
         }
       })();
     }
@@ -5,9 +5,11 @@ import { useSettings } from '../state/SettingsContext';
 import { handleDirectoryError, validateDirectory } from '../utils/directoryUtils';
 
 export const usePosDirectory = () => {
-  const { setSettings } = useSettings();
+  const { settings, setSettings } = useSettings();
   // Track validation error state
   const [error, setError] = useState<string | null>(null);
+  // Track warnings about the selected directory's filesystem
+  const [warnings, setWarnings] = useState<string[]>([]);
   // Track directory validation progress
   const [isValidating, setIsValidating] = useState(false);
 
@@ -29,6 +31,7 @@ export const usePosDirectory = () => {
     try {
       setIsValidating(true);
       setError(null);
+      setWarnings([]);
 
       // Open native directory picker
       const selected = await open({
@@ -44,7 +47,11 @@ export const usePosDirectory = () => {
 
       const dir = selected as string;
       // Validate custom directory
-      const validationResult = await validateDirectory(dir);
+      const validationResult = await validateDirectory(
+        dir,
+        settings.numUnits,
+        settings.maxFileSize ? settings.maxFileSize * 1024 * 1024 : undefined
+      );
 
       if (validationResult.isValid) {
         // Update settings with validated directory
@@ -52,6 +59,7 @@ export const usePosDirectory = () => {
           ...prev,
           selectedDir: dir,
         }));
+        setWarnings(validationResult.warnings ?? []);
       } else {
         // Handle validation failure
         setError(validationResult.error || 'Invalid directory selected');
@@ -71,6 +79,7 @@ export const usePosDirectory = () => {
   return {
     selectDirectory,
     error,
+    warnings,
     isValidating,
   };
 }
\ No newline at end of file
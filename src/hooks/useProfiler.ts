@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api';
+import { listen } from '@tauri-apps/api/event';
 import { join } from '@tauri-apps/api/path';
 import { useEffect, useState } from 'react';
 
@@ -14,8 +15,16 @@ import {
   Benchmark,
   BenchmarkSettings,
   BenchmarkStatus,
+  HistoryFilter,
+  OptimizerOptions,
   ProfilerConfig,
+  ProfilerHistoryEntry,
+  ProfilerProgress,
   ProfilerResult,
+  ProfilerSweepResult,
+  ProvingRecommendation,
+  SweepProgress,
+  SweepRange,
 } from '../types/profiler';
 
 /**
@@ -28,6 +37,13 @@ const DEFAULT_CONFIG: ProfilerConfig = {
   duration: 10,
 };
 
+/**
+ * Run id under which a benchmark is started and can be cancelled
+ * @param {Benchmark} benchmark - Benchmark to get the run id for
+ * @returns {string} Run id of the benchmark
+ */
+const benchmarkRunId = (benchmark: Benchmark): string => `profiler-${benchmark.id}`;
+
 /**
  * Custom hook for managing POS profiler operations and state
  * @returns {Object} Object containing profiler state and control functions
@@ -41,6 +57,9 @@ export const useProfiler = () => {
   const [maxCores, setMaxCores] = useState(0);                                    // Maximum available CPU cores
   const [config, setConfig] = useState<ProfilerConfig>(DEFAULT_CONFIG);           // Current profiler configuration
   const [benchmarks, setBenchmarks] = useState<Benchmark[]>([]);                  // List of completed and ongoing benchmarks
+  const [isSweeping, setIsSweeping] = useState(false);                           // Whether a sweep is running
+  const [sweepRunId, setSweepRunId] = useState<string | null>(null);             // Run id of the running sweep
+  const [reuseCached, setReuseCached] = useState(true);                           // Whether matching history results replace new runs
   const [benchmarkSettings, setBenchmarkSettings] = useState<BenchmarkSettings>(   // Current benchmark parameters
     {
       nonces: 288,  // Default number of nonces to process
@@ -71,6 +90,25 @@ export const useProfiler = () => {
     initialize();
   }, []);
 
+  /**
+   * Tracks the elapsed time of running benchmarks and logs the profiler's output
+   */
+  useEffect(() => {
+    const unlisten = listen<ProfilerProgress>('profiler-progress', (event) => {
+      const { run_id, elapsed_s, line } = event.payload;
+      if (line) {
+        updateConsole('profiler', line);
+      }
+      setBenchmarks((prev) =>
+        prev.map((b) => (benchmarkRunId(b) === run_id ? { ...b, elapsed_s } : b))
+      );
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [updateConsole]);
+
   /**
    * Executes a single benchmark with specified parameters
    * @param {Benchmark} benchmark - Benchmark configuration to run
@@ -94,18 +132,31 @@ export const useProfiler = () => {
         settings.selectedDir || (settings.defaultDir as string);
       const dataFilePath = await join(currentDir, 'profiler-data');
 
-      updateConsole('profiler', `Running profiler: ${benchmark.nonces} nonces, ${benchmark.threads} threads at ${dataFilePath}. Data size: ${config.data_size} GiB, duration: ${config.duration} seconds`);
+      const runConfig = { ...config, data_file: dataFilePath };
+      const cached = reuseCached
+        ? await invoke<ProfilerHistoryEntry | null>('get_cached_profiler_result', {
+            nonces: benchmark.nonces,
+            threads: benchmark.threads,
+            config: runConfig,
+          })
+        : null;
 
-      const result = await invoke<ProfilerResult>('run_profiler', {
-        nonces: benchmark.nonces,
-        threads: benchmark.threads,
-        config: {
-          ...config,
-          data_file: dataFilePath,
-        },
-      });
+      let result: ProfilerResult;
+      if (cached) {
+        result = { speed_gib_s: cached.result.speed_gib_s };
+        updateConsole('profiler', `Reusing result from ${new Date(cached.recorded_at * 1000).toLocaleString()} measured on the same hardware: ${JSON.stringify(cached.result)}`);
+      } else {
+        updateConsole('profiler', `Running profiler: ${benchmark.nonces} nonces, ${benchmark.threads} threads at ${dataFilePath}. Data size: ${config.data_size} GiB, duration: ${config.duration} seconds`);
 
-      updateConsole('profiler', `Profiler result: ${JSON.stringify(result)}`);
+        result = await invoke<ProfilerResult>('run_profiler', {
+          nonces: benchmark.nonces,
+          threads: benchmark.threads,
+          config: runConfig,
+          runId: benchmarkRunId(benchmark),
+        });
+
+        updateConsole('profiler', `Profiler result: ${JSON.stringify(result)}`);
+      }
 
       setBenchmarks((prev) =>
         prev.map((b) =>
@@ -115,6 +166,7 @@ export const useProfiler = () => {
                 ...result,
                 status: BenchmarkStatus.Complete,
                 data_file: currentDir,
+                cached_at: cached?.recorded_at,
               }
             : b
         )
@@ -152,6 +204,225 @@ export const useProfiler = () => {
     await runBenchmark(newBenchmark);
   };
 
+  /**
+   * Benchmarks against the PoS data of the selected directory
+   * The largest complete data file is profiled, so the speed reflects the real disk and data.
+   * The profiler cannot be kept from writing to it; the run fails if the data size exceeds the file or the file changed.
+   * @throws {Error} If no directory is selected for profiling
+   */
+  const runPosDataBenchmark = async () => {
+    if (!settings.selectedDir) {
+      throw new Error('No PoS data directory selected for profiling');
+    }
+
+    const benchmark: Benchmark = {
+      id: Date.now(),
+      nonces: benchmarkSettings.nonces,
+      threads: benchmarkSettings.threads,
+      status: BenchmarkStatus.Running,
+      data_file: settings.selectedDir,
+    };
+    setBenchmarks((prev) => [...prev, benchmark]);
+    updateConsole('profiler', `Running profiler on the PoS data in ${settings.selectedDir}: ${benchmark.nonces} nonces, ${benchmark.threads} threads. Data size: ${config.data_size} GiB, duration: ${config.duration} seconds`);
+
+    try {
+      const result = await invoke<ProfilerResult>('run_profiler_on_pos_data', {
+        dir: settings.selectedDir,
+        nonces: benchmark.nonces,
+        threads: benchmark.threads,
+        config: { data_size: config.data_size, duration: config.duration },
+        runId: benchmarkRunId(benchmark),
+      });
+      updateConsole('profiler', `Profiler result: ${JSON.stringify(result)}`);
+      setBenchmarks((prev) =>
+        prev.map((b) =>
+          b.id === benchmark.id ? { ...b, ...result, status: BenchmarkStatus.Complete } : b
+        )
+      );
+    } catch (error) {
+      updateConsole('profiler', `Profiler failed: ${error}`);
+      setBenchmarks((prev) =>
+        prev.map((b) =>
+          b.id === benchmark.id ? { ...b, status: BenchmarkStatus.Error, error: String(error) } : b
+        )
+      );
+    }
+  };
+
+  /**
+   * Adds every point measured by a sweep to the benchmarks as soon as it finishes
+   * @param {string} currentDir - Directory the profiler data file is stored in
+   * @returns {Promise<() => void>} Function to stop listening
+   */
+  const listenSweepProgress = (currentDir: string) =>
+    listen<SweepProgress>('profiler-sweep-progress', (event) => {
+      const { result, completed, total } = event.payload;
+      updateConsole('profiler', `Sweep point ${completed}/${total}: ${JSON.stringify(result)}`);
+      setBenchmarks((prev) => [
+        ...prev,
+        {
+          id: Date.now() + prev.length,
+          nonces: result.nonces,
+          threads: result.threads,
+          speed_gib_s: result.speed_gib_s,
+          status: BenchmarkStatus.Complete,
+          data_file: currentDir,
+        },
+      ]);
+    });
+
+  /**
+   * Runs the profiler over ranges of nonces and threads in the backend
+   * Every measured point is added to the benchmarks as soon as it finishes.
+   * @param {SweepRange} nonces - Range of nonce counts (multiples of 16)
+   * @param {SweepRange} threads - Range of thread counts
+   * @returns {Promise<ProfilerSweepResult | undefined>} Result matrix and fastest points, undefined on failure
+   * @throws {Error} If no directory is selected for profiling
+   */
+  const runSweep = async (
+    nonces: SweepRange,
+    threads: SweepRange
+  ): Promise<ProfilerSweepResult | undefined> => {
+    if (!settings.selectedDir && !settings.defaultDir) {
+      throw new Error('No directory selected for profiling');
+    }
+
+    const currentDir = settings.selectedDir || (settings.defaultDir as string);
+    const dataFilePath = await join(currentDir, 'profiler-data');
+
+    updateConsole('profiler', `Running profiler sweep: ${nonces.start}-${nonces.end} nonces, ${threads.start}-${threads.end} threads at ${dataFilePath}`);
+
+    const runId = `profiler-sweep-${Date.now()}`;
+    setIsSweeping(true);
+    setSweepRunId(runId);
+    const unlisten = await listenSweepProgress(currentDir);
+
+    try {
+      const sweep = await invoke<ProfilerSweepResult>('run_profiler_sweep', {
+        nonces,
+        threads,
+        config: {
+          ...config,
+          data_file: dataFilePath,
+        },
+        runId,
+      });
+      const fastest = sweep.fastest
+        .map((r) => `${r.nonces} nonces: ${r.threads} threads (${r.speed_gib_s.toFixed(2)} GiB/s)`)
+        .join(', ');
+      updateConsole('profiler', `Fastest configurations: ${fastest}`);
+      return sweep;
+    } catch (error) {
+      updateConsole('profiler', `Profiler sweep failed: ${error}`);
+      return undefined;
+    } finally {
+      unlisten();
+      setIsSweeping(false);
+      setSweepRunId(null);
+    }
+  };
+
+  /**
+   * Searches nonces and threads for the configuration most likely to prove within the cycle gap
+   * Runs as a sweep: measured points are added to the benchmarks and it can be cancelled with cancelSweep.
+   * @param {OptimizerOptions} options - Optional share of cores to keep free, nonce range and disk read speed
+   * @returns {Promise<ProvingRecommendation | undefined>} Recommended point and all candidates, undefined on failure
+   * @throws {Error} If no directory is selected for profiling
+   */
+  const recommendConfig = async (
+    options?: OptimizerOptions
+  ): Promise<ProvingRecommendation | undefined> => {
+    if (!settings.selectedDir && !settings.defaultDir) {
+      throw new Error('No directory selected for profiling');
+    }
+
+    const currentDir = settings.selectedDir || (settings.defaultDir as string);
+    const dataFilePath = await join(currentDir, 'profiler-data');
+
+    updateConsole('profiler', `Searching proving configuration for ${settings.numUnits} units at ${dataFilePath}`);
+
+    const runId = `profiler-optimize-${Date.now()}`;
+    setIsSweeping(true);
+    setSweepRunId(runId);
+    const unlisten = await listenSweepProgress(currentDir);
+
+    try {
+      const recommendation = await invoke<ProvingRecommendation>('optimize_proving_config', {
+        numUnits: settings.numUnits,
+        options,
+        config: {
+          ...config,
+          data_file: dataFilePath,
+        },
+        runId,
+      });
+      const { result, estimate } = recommendation.recommended;
+      updateConsole(
+        'profiler',
+        `Recommended ${result.nonces} nonces with ${result.threads} threads: ` +
+          `${(estimate.probability_in_deadline * 100).toFixed(2)}% chance to prove in time (${estimate.verdict}, limited by ${estimate.limiting_factor})`
+      );
+      return recommendation;
+    } catch (error) {
+      updateConsole('profiler', `Proving configuration search failed: ${error}`);
+      return undefined;
+    } finally {
+      unlisten();
+      setIsSweeping(false);
+      setSweepRunId(null);
+    }
+  };
+
+  /**
+   * Cancels a running benchmark; the profiler is killed and its data file cleaned up
+   * @param {Benchmark} benchmark - The running benchmark to cancel
+   */
+  const cancelBenchmark = async (benchmark: Benchmark) => {
+    try {
+      await invoke('cancel_profiler', { runId: benchmarkRunId(benchmark) });
+    } catch (error) {
+      console.error('Error cancelling benchmark:', error);
+    }
+  };
+
+  /**
+   * Cancels the running sweep, if any
+   */
+  const cancelSweep = async () => {
+    if (!sweepRunId) return;
+    try {
+      await invoke('cancel_profiler', { runId: sweepRunId });
+    } catch (error) {
+      console.error('Error cancelling profiler sweep:', error);
+    }
+  };
+
+  /**
+   * Lists stored profiler results
+   * @param {HistoryFilter} filter - Optional criteria the entries must match
+   * @returns {Promise<ProfilerHistoryEntry[]>} Matching entries, oldest first
+   */
+  const listHistory = (filter?: HistoryFilter) =>
+    invoke<ProfilerHistoryEntry[]>('list_profiler_history', { filter });
+
+  /**
+   * Deletes stored profiler results
+   * @param {string[]} ids - Ids of the entries to delete
+   * @returns {Promise<number>} Number of entries deleted
+   */
+  const deleteHistory = (ids: string[]) =>
+    invoke<number>('delete_profiler_history', { ids });
+
+  /**
+   * Exports stored profiler results to a file
+   * @param {string} path - File to write
+   * @param {'csv' | 'json'} format - Export format
+   * @param {HistoryFilter} filter - Optional criteria the exported entries must match
+   * @returns {Promise<number>} Number of exported entries
+   */
+  const exportHistory = (path: string, format: 'csv' | 'json', filter?: HistoryFilter) =>
+    invoke<number>('export_profiler_history', { path, format, filter });
+
   /**
    * Updates global settings with the results of a completed benchmark
    * @param {Benchmark} benchmark - The completed benchmark to apply
@@ -190,7 +461,17 @@ export const useProfiler = () => {
     updateConfig,               // Function to update profiler configuration
     updateBenchmarkSettings,    // Function to update benchmark settings
     runCustomBenchmark,        // Function to run a new benchmark
+    runSweep,                  // Function to run a sweep over nonces and threads
+    runPosDataBenchmark,       // Function to benchmark against existing PoS data
+    recommendConfig,           // Function to search for the best proving configuration
+    cancelBenchmark,           // Function to cancel a running benchmark
+    cancelSweep,               // Function to cancel the running sweep
+    reuseCached,               // Whether matching history results replace new runs
+    setReuseCached,            // Function to toggle reuse of history results
+    listHistory,               // Function to list stored profiler results
+    deleteHistory,             // Function to delete stored profiler results
+    exportHistory,             // Function to export stored profiler results as CSV or JSON
     selectBenchmark,           // Function to apply a benchmark's results
-    isRunning: benchmarks.some((b) => b.status === BenchmarkStatus.Running), // Whether any benchmark is currently running
+    isRunning: isSweeping || benchmarks.some((b) => b.status === BenchmarkStatus.Running), // Whether any benchmark is currently running
   };
 };
@@ -110,7 +110,7 @@ const Generate: React.FC = () => {
   // Detect providers on component mount
   useEffect(() => {
     const detectProviders = async () => {
-      await run(['-printProviders'], updateConsole);
+      await run(updateConsole);
     };
     detectProviders();
   }, [run, updateConsole]);
@@ -204,12 +204,12 @@ const Generate: React.FC = () => {
         updateConsole
       );
 
-      if (response && response.process_id) {
+      if (response && response.job_id) {
         updateConsole(
           'generate',
-          `POS generation started with process ID: ${response.process_id}`
+          `POS generation started as job ${response.job_id} with process ID: ${response.process_id}`
         );
-        startProcess(response.process_id);
+        startProcess(response.job_id);
         setShowSuccessModal(true);
 
         /**
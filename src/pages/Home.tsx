@@ -7,6 +7,7 @@ import BackgroundImage from "../assets/home.png";
 import { HoverAccordionMenu } from "../components/accordion";
 import { Button } from "../components/button";
 import Image from "../components/image";
+import { InterruptedJobsModal } from "../components/InterruptedJobsModal";
 import { Background } from "../styles/containers";
 import { Subheader, Title } from "../styles/texts";
 
@@ -71,6 +72,7 @@ const Home: React.FC = () => {
           buttons={CheckButtons}
         />
       </MenuContainer>
+      <InterruptedJobsModal />
     </>
   );
 };
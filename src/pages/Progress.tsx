@@ -58,12 +58,13 @@ const ButtonsContainer = styled.div`
 
 const Progress: React.FC = () => {
   const navigate = useNavigate();
-  const { processState, stopProcess } = usePOSProcess();
+  const { processState, stopProcess, setPaused } = usePOSProcess();
   const {
     stage,
     details,
     isError,
     isRunning,
+    isPaused,
     fileProgress,
     progress,
   } = processState;
@@ -96,6 +97,14 @@ const Progress: React.FC = () => {
     }
   };
 
+  const handleTogglePause = async () => {
+    try {
+      await setPaused(!isPaused);
+    } catch (error) {
+      console.error('Failed to pause/resume POS generation:', error);
+    }
+  };
+
   // Calculate total files
   const totalFiles = calculateNumFiles(settings.numUnits, settings.maxFileSize);
 
@@ -171,7 +180,13 @@ const Progress: React.FC = () => {
             width={590}
             blurred
             backgroundColor={Colors.whiteOpaque}
-            heading={stage === Stage.Complete ? 'Generated' : 'Generating...'}
+            heading={
+              stage === Stage.Complete
+                ? 'Generated'
+                : isPaused
+                  ? 'Paused'
+                  : 'Generating...'
+            }
             footer={
               `${formatSizeUnits((progress / 100) * getSizePerUnit(SizeConstants.DEFAULT_LABELS_PER_UNIT) * (settings.numUnits ?? 0))} / ${totalSize}`
             }
@@ -187,6 +202,14 @@ const Progress: React.FC = () => {
             height={52}
             disabled={stage === Stage.Complete || stage === Stage.Error}
           />
+          {stage === Stage.Processing && (
+            <Button
+              label={isPaused ? 'Resume Generation' : 'Pause Generation'}
+              onClick={handleTogglePause}
+              width={250}
+              height={52}
+            />
+          )}
           {stage === Stage.Complete && (
             <Button
               label="Init another PoS data"
@@ -15,7 +15,13 @@ import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/tauri';
 
 import { Settings } from '../state/SettingsContext';
-import { buildPostCliArgs, validateSettings } from '../utils/postcliUtils';
+import { PostCliConfig } from '../types/postcliConfig';
+import {
+  buildPostCliConfig,
+  formatPostCliCommand,
+  formatPostCliError,
+  validateSettings,
+} from '../utils/postcliUtils';
 import { base64ToHex } from '../utils/hexUtils';
 
 /**
@@ -25,6 +31,9 @@ import { base64ToHex } from '../utils/hexUtils';
 export interface PostCliResponse {
   stdout: string;
   stderr: string;
+  /** Exit code, null if postcli was killed by a signal */
+  exit_code: number | null;
+  /** Whether postcli exited with code 0 */
   success: boolean;
 }
 
@@ -58,12 +67,132 @@ export interface AtxIdResponse {
  * @interface DetachedProcessResponse
  */
 export interface DetachedProcessResponse {
+  /** Registry ID of the job owning the detached postcli process */
+  job_id: number;
   /** Process ID of the detached postcli process */
   process_id: number;
   /** Status message about the process */
   message: string;
 }
 
+/**
+ * Outcome of stopping a detached postcli job
+ * @interface StopResult
+ */
+export interface StopResult {
+  job_id: number;
+  process_id: number;
+  /** Exit code, if postcli exited normally */
+  code: number | null;
+  /** Terminating signal, if postcli was killed by one */
+  signal: number | null;
+  /** Whether postcli had to be killed after the grace period */
+  escalated: boolean;
+  /** Index of the last fully written postdata_N.bin, if any */
+  last_completed_file: number | null;
+}
+
+/**
+ * Persisted record of a postcli job interrupted by the app closing or crashing
+ * @interface InterruptedJob
+ */
+export interface InterruptedJob {
+  id: string;
+  args: string[];
+  datadir: string;
+  pid: number;
+  /** Start time as seconds since the Unix epoch */
+  started_at: number;
+  last_progress: {
+    bytes_done: number;
+    total_bytes: number;
+    percentage: number;
+    updated_at: number;
+  } | null;
+  /** Files postcli wrote its stdout and stderr to */
+  output: {
+    stdout: string;
+    stderr: string;
+  } | null;
+  /** Whether the process was paused when the app closed */
+  paused: boolean;
+}
+
+/**
+ * Entry of the initialization queue
+ * @interface QueueEntry
+ */
+export interface QueueEntry {
+  id: number;
+  config: PostCliConfig;
+  state: 'pending' | 'starting' | 'running' | 'completed' | 'failed';
+  /** Set while running */
+  job_id?: number;
+  record_id?: string;
+  /** Set when failed */
+  message?: string;
+}
+
+/**
+ * State of the initialization queue, also the payload of 'postcli-queue' events
+ * @interface QueueSnapshot
+ */
+export interface QueueSnapshot {
+  entries: QueueEntry[];
+  max_parallel_per_provider: number;
+}
+
+/**
+ * Aggregated view of an initialization split across providers,
+ * also the payload of 'postcli-split-progress' events
+ * @interface SplitProgress
+ */
+export interface SplitProgress {
+  split_id: number;
+  datadir: string;
+  state: 'initializing' | 'finalizing' | 'completed' | 'failed';
+  /** Job writing the metadata, set while finalizing */
+  job_id?: number;
+  /** Set when failed */
+  message?: string;
+  bytes_done: number;
+  total_bytes: number;
+  percentage: number;
+  average_rate: number;
+  eta_secs: number | null;
+  slices: {
+    provider: number;
+    from_file: number;
+    to_file: number;
+    job_id: number;
+    status: { state: 'running' | 'paused' | 'exited'; code?: number | null; signal?: number | null };
+    bytes_done: number;
+    total_bytes: number;
+  }[];
+}
+
+/**
+ * Log file of a postcli job
+ * @interface JobLogInfo
+ */
+export interface JobLogInfo {
+  /** Name of the log, e.g. postcli-20241217-223457-1234 */
+  name: string;
+  /** Total size including rotated parts in bytes */
+  size: number;
+  /** Time of the last write as seconds since the Unix epoch */
+  modified_at: number;
+}
+
+/**
+ * Line of a job log matching a search
+ * @interface LogMatch
+ */
+export interface LogMatch {
+  line_number: number;
+  line: string;
+}
+
 /**
  * Fetches the latest ATX ID from the network
  * Makes a POST request to the Spacemesh API to get the highest activation
@@ -132,10 +261,11 @@ export const fetchLatestAtxId = async (): Promise<AtxIdResponse> => {
 /**
  * Executes postcli command synchronously with the provided settings
  * Performs full validation before execution and provides real-time output
+ * Only available in developer mode, initializations run detached otherwise
  *
  * Process:
  * 1. Validates all settings
- * 2. Builds command arguments
+ * 2. Builds the postcli configuration (validated again by the backend)
  * 3. Executes command and streams output
  * 4. Handles any errors or validation failures
  *
@@ -160,9 +290,9 @@ export const executePostCli = async (
     throw new Error(validationError);
   }
 
-  // Build command arguments
-  const args = buildPostCliArgs(settings);
-  if (!args) {
+  // Build postcli configuration
+  const config = buildPostCliConfig(settings);
+  if (!config) {
     const error = 'Cannot proceed: Valid ATX ID is required';
     console.error(error);
     if (updateConsole) {
@@ -171,7 +301,7 @@ export const executePostCli = async (
     throw new Error(error);
   }
 
-  const commandStr = `./postcli ${args.join(' ')}`;
+  const commandStr = formatPostCliCommand(config);
 
   console.log('Executing command:', commandStr);
   if (updateConsole) {
@@ -179,9 +309,9 @@ export const executePostCli = async (
   }
 
   try {
-    const response = await invoke<{ stdout: string; stderr: string }>(
+    const response = await invoke<PostCliResponse>(
       'run_postcli_command',
-      { args }
+      { config }
     );
 
     // Handle stdout output
@@ -199,18 +329,12 @@ export const executePostCli = async (
       updateConsole?.(commandStr, '> Command completed with no output');
     }
 
-    return {
-      ...response,
-      success:
-        !response.stderr?.includes('error') &&
-        !response.stderr?.includes('Error'),
-    };
+    return response;
   } catch (error) {
-    const errorMessage =
-      error instanceof Error ? error.message : 'An unknown error occurred';
+    const errorMessage = formatPostCliError(error);
     console.error('Error executing postcli:', error);
     updateConsole?.(commandStr, `> Error:\n${errorMessage}`);
-    throw error;
+    throw new Error(errorMessage);
   }
 };
 
@@ -245,9 +369,9 @@ export const executePostCliDetached = async (
     throw new Error(validationError);
   }
 
-  // Build command arguments
-  const args = buildPostCliArgs(settings);
-  if (!args) {
+  // Build postcli configuration
+  const config = buildPostCliConfig(settings);
+  if (!config) {
     const error = 'Cannot proceed: Valid ATX ID is required';
     console.error(error);
     if (updateConsole) {
@@ -256,7 +380,7 @@ export const executePostCliDetached = async (
     throw new Error(error);
   }
 
-  const commandStr = `./postcli ${args.join(' ')} (detached)`;
+  const commandStr = `${formatPostCliCommand(config)} (detached)`;
 
   console.log('Executing command in detached mode:', commandStr);
   if (updateConsole) {
@@ -277,20 +401,20 @@ export const executePostCliDetached = async (
           updateConsole('postcli-detached', event.payload);
         }
 
-        // Emit custom event for process state updates
-        // This allows other components to react to progress updates
+        // Forward the raw line to the process log
+        // Stage changes arrive separately as typed 'postcli-progress' events from the backend
         window.dispatchEvent(
-          new CustomEvent('postcli-progress', {
+          new CustomEvent('postcli-log-line', {
             detail: event.payload,
           })
         );
       }
     });
 
-    console.log('Invoking Tauri detached command with args:', args);
+    console.log('Invoking Tauri detached command with config:', config);
     const response = await invoke<DetachedProcessResponse>(
       'run_postcli_detached',
-      { args }
+      { config }
     );
     console.log('Detached command response:', response);
 
@@ -300,8 +424,7 @@ export const executePostCliDetached = async (
 
     return response;
   } catch (error) {
-    const errorMessage =
-      error instanceof Error ? error.message : 'An unknown error occurred';
+    const errorMessage = formatPostCliError(error);
     console.error('Error executing postcli in detached mode:', error);
     if (updateConsole) {
       updateConsole(commandStr, `> Error:\n${errorMessage}`);
@@ -309,43 +432,53 @@ export const executePostCliDetached = async (
     if (unlistenCallback) {
       unlistenCallback(); // Clean up listener on error
     }
-    throw error;
+    throw new Error(errorMessage);
   }
 };
 
 /**
- * Stops a running detached postcli process
- * Attempts graceful shutdown of the specified process
+ * Stops a running detached postcli job
+ * Attempts graceful shutdown of the process owned by the specified job
  *
  * Process:
  * 1. Sends termination signal to process
- * 2. Waits for confirmation
- * 3. Reports success or failure
+ * 2. Escalates to a forced kill after the grace period
+ * 3. Reports the final exit status and the last completed file
  *
- * @param {number} processId - ID of the process to stop
+ * @param {number} jobId - Registry ID of the job to stop
  * @param {Function} updateConsole - Optional callback for console updates
+ * @returns {Promise<StopResult>} Final exit status of the job
  * @throws {Error} If process termination fails
  */
 export const stopPostCliProcess = async (
-  processId: number,
+  jobId: number,
   updateConsole?: (command: string, output: string) => void
-): Promise<void> => {
-  console.log('Attempting to stop postcli process:', processId);
-  const commandStr = `Stop postcli process ${processId}`;
+): Promise<StopResult> => {
+  console.log('Attempting to stop postcli job:', jobId);
+  const commandStr = `Stop postcli job ${jobId}`;
 
   if (updateConsole) {
-    updateConsole(commandStr, `> Attempting to stop process ${processId}...`);
+    updateConsole(commandStr, `> Attempting to stop job ${jobId}...`);
   }
 
   try {
-    const response = await invoke<string>('stop_postcli_process', {
-      pid: processId,
+    const response = await invoke<StopResult>('stop_postcli_process', {
+      jobId,
     });
     console.log('Stop process response:', response);
 
     if (updateConsole) {
-      updateConsole(commandStr, `> ${response}`);
+      const lastFile =
+        response.last_completed_file === null
+          ? 'no file was completed'
+          : `last completed file: postdata_${response.last_completed_file}.bin`;
+      updateConsole(
+        commandStr,
+        `> Process ${response.process_id} stopped${response.escalated ? ' (killed after grace period)' : ''}, ${lastFile}`
+      );
     }
+
+    return response;
   } catch (error) {
     const errorMessage =
       error instanceof Error ? error.message : 'An unknown error occurred';
@@ -358,37 +491,156 @@ export const stopPostCliProcess = async (
 };
 
 /**
- * Low-level function to execute postcli commands directly with arguments
- * Used by other service functions and external components
+ * Pauses or resumes a running detached postcli job
+ * The backend suspends/continues the process and emits a 'postcli-state' event
  *
- * Features:
- * - Direct command execution with raw arguments
- * - Real-time output streaming
- * - Comprehensive error handling
- * - Console output formatting
+ * @param {number} jobId - Registry ID of the job
+ * @param {boolean} pause - Whether to pause (true) or resume (false) the job
+ * @param {Function} updateConsole - Optional callback for console updates
+ * @throws {Error} If the job cannot be paused or resumed
+ */
+export const setPostCliJobPaused = async (
+  jobId: number,
+  pause: boolean,
+  updateConsole?: (command: string, output: string) => void
+): Promise<void> => {
+  const commandStr = `${pause ? 'Pause' : 'Resume'} postcli job ${jobId}`;
+
+  try {
+    const response = await invoke<string>(
+      pause ? 'pause_postcli_job' : 'resume_postcli_job',
+      { jobId }
+    );
+    updateConsole?.(commandStr, `> ${response}`);
+  } catch (error) {
+    const errorMessage =
+      error instanceof Error ? error.message : String(error);
+    console.error(`Error during ${commandStr}:`, error);
+    updateConsole?.(commandStr, `> Error:\n${errorMessage}`);
+    throw error;
+  }
+};
+
+/**
+ * Lists postcli jobs interrupted by the app closing or crashing
+ * Running jobs from a previous session are re-attached by the backend on startup,
+ * jobs whose process is gone are reported here so they can be resumed
+ *
+ * @returns {Promise<InterruptedJob[]>} Interrupted job records
+ */
+export const listInterruptedJobs = async (): Promise<InterruptedJob[]> => {
+  return invoke<InterruptedJob[]>('list_interrupted_jobs');
+};
+
+/**
+ * Resumes an interrupted postcli job with its original arguments
+ * postcli continues from the files already present in the data directory
+ *
+ * @param {string} recordId - ID of the interrupted job record
+ * @returns {Promise<DetachedProcessResponse>} Information about the new job
+ */
+export const resumeInterruptedJob = async (
+  recordId: string
+): Promise<DetachedProcessResponse> => {
+  return invoke<DetachedProcessResponse>('resume_interrupted_job', { recordId });
+};
+
+/**
+ * Forgets an interrupted postcli job
+ *
+ * @param {string} recordId - ID of the interrupted job record
+ */
+export const discardInterruptedJob = async (recordId: string): Promise<void> => {
+  await invoke('discard_interrupted_job', { recordId });
+};
+
+/**
+ * Adds an initialization to the end of the queue
+ * The backend starts it right away if its provider is idle
+ *
+ * @param {PostCliConfig} config - Full postcli configuration
+ * @returns {Promise<QueueEntry>} The new queue entry
+ */
+export const addToQueue = async (config: PostCliConfig): Promise<QueueEntry> => {
+  return invoke<QueueEntry>('queue_add', { config });
+};
+
+/**
+ * Removes an entry that is not running from the queue
+ *
+ * @param {number} entryId - ID of the queue entry
+ */
+export const removeFromQueue = async (entryId: number): Promise<void> => {
+  await invoke('queue_remove', { entryId });
+};
+
+/**
+ * Moves a queue entry to another position
+ *
+ * @param {number} entryId - ID of the queue entry
+ * @param {number} position - New zero-based position
+ */
+export const reorderQueue = async (entryId: number, position: number): Promise<void> => {
+  await invoke('queue_reorder', { entryId, position });
+};
+
+/**
+ * Returns the current initialization queue
  *
- * @param {string[]} args - Command line arguments for postcli
+ * @returns {Promise<QueueSnapshot>} Queue entries and settings
+ */
+export const listQueue = async (): Promise<QueueSnapshot> => {
+  return invoke<QueueSnapshot>('queue_list');
+};
+
+/**
+ * Sets how many queued initializations may run at once on one provider
+ *
+ * @param {number} maxParallelPerProvider - Maximum parallel jobs per provider
+ */
+export const setQueueParallelism = async (maxParallelPerProvider: number): Promise<void> => {
+  await invoke('queue_set_parallelism', { maxParallelPerProvider });
+};
+
+/**
+ * Starts one initialization split across several providers
+ * Files are partitioned by provider speed; providers without a measured
+ * speed get an equal share
+ *
+ * @param {PostCliConfig} config - Configuration of the whole initialization, its id is required
+ * @param {Array} providers - Provider IDs with their measured speeds
+ * @returns {Promise<SplitProgress>} Initial view of the split
+ */
+export const runPostCliSplit = async (
+  config: PostCliConfig,
+  providers: { provider: number; speed: number | null }[]
+): Promise<SplitProgress> => {
+  return invoke<SplitProgress>('run_postcli_split', { config, providers });
+};
+
+/**
+ * Invokes a synchronous postcli backend command and reports its output
+ *
+ * @param {string} command - Name of the backend command
+ * @param {Record<string, unknown>} payload - Command arguments
+ * @param {string} commandStr - Command line shown in the console
  * @param {Function} updateConsole - Optional callback for console updates
  * @returns {Promise<PostCliResponse>} Command execution results
- * @throws {Error} If command execution fails
+ * @throws {Error} If validation or command execution fails
  */
-export const callPostCli = async (
-  args: string[],
+const invokeSyncPostCli = async (
+  command: string,
+  payload: Record<string, unknown>,
+  commandStr: string,
   updateConsole?: (command: string, output: string) => void
 ): Promise<PostCliResponse> => {
-  console.log('callPostCli called with args:', args);
-  const commandStr = `./postcli ${args.join(' ')}`;
-
   if (updateConsole) {
     updateConsole(commandStr, '> Executing command...');
   }
 
   try {
-    console.log('Invoking Tauri command with args:', args);
-    const response = await invoke<{ stdout: string; stderr: string }>(
-      'run_postcli_command',
-      { args }
-    );
+    console.log(`Invoking Tauri command ${command} with:`, payload);
+    const response = await invoke<PostCliResponse>(command, payload);
     console.log('Command response:', response);
 
     // Handle stdout output
@@ -407,19 +659,156 @@ export const callPostCli = async (
       updateConsole?.(commandStr, '> Command completed with no output');
     }
 
-    return {
-      ...response,
-      success:
-        !response.stderr?.includes('error') &&
-        !response.stderr?.includes('Error'),
-    };
+    if (!response.success) {
+      updateConsole?.(commandStr, `> postcli exited with code ${response.exit_code ?? 'unknown (killed)'}`);
+    }
+
+    return response;
   } catch (error) {
-    const errorMessage =
-      error instanceof Error ? error.message : 'An unknown error occurred';
+    const errorMessage = formatPostCliError(error);
     console.error('Error executing postcli:', error);
     if (updateConsole) {
       updateConsole(commandStr, `> Error:\n${errorMessage}`);
     }
-    throw error;
+    throw new Error(errorMessage);
   }
 };
+
+/**
+ * Lists the OpenCL providers available to postcli
+ * postcli is killed if it does not answer within the timeout
+ *
+ * @param {Function} updateConsole - Optional callback for console updates
+ * @param {string} callId - Optional ID to cancel the call with cancelPostCliCall
+ * @param {number} timeoutSecs - Optional timeout, 60 seconds by default
+ * @returns {Promise<PostCliResponse>} Provider listing on stdout
+ */
+export const printPostCliProviders = async (
+  updateConsole?: (command: string, output: string) => void,
+  callId?: string,
+  timeoutSecs?: number
+): Promise<PostCliResponse> => {
+  return invokeSyncPostCli(
+    'print_postcli_providers',
+    { callId, timeoutSecs },
+    './postcli -printProviders',
+    updateConsole
+  );
+};
+
+/**
+ * Returns the version of the bundled postcli
+ *
+ * @returns {Promise<PostCliResponse>} Version information
+ */
+export const getPostCliVersion = async (): Promise<PostCliResponse> => {
+  return invokeSyncPostCli('get_postcli_version', {}, './postcli -version');
+};
+
+/**
+ * Returns the usage information of the bundled postcli
+ *
+ * @returns {Promise<PostCliResponse>} Usage information
+ */
+export const getPostCliHelp = async (): Promise<PostCliResponse> => {
+  return invokeSyncPostCli('get_postcli_help', {}, './postcli -help');
+};
+
+/**
+ * Starts proof generation over an initialized data directory as a detached job
+ *
+ * @param {Settings} settings - POS configuration settings of the initialization
+ * @returns {Promise<DetachedProcessResponse>} Information about the new job
+ * @throws {Error} If the settings are incomplete or the backend rejects them
+ */
+export const generatePostCliProof = async (
+  settings: Settings
+): Promise<DetachedProcessResponse> => {
+  const config = buildPostCliConfig(settings);
+  if (!config) {
+    throw new Error('Cannot proceed: Valid ATX ID is required');
+  }
+  try {
+    return await invoke<DetachedProcessResponse>('run_postcli_genproof', { config });
+  } catch (error) {
+    throw new Error(formatPostCliError(error));
+  }
+};
+
+/**
+ * Cancels a running postcli call, killing its process
+ *
+ * @param {string} callId - ID the call was started with
+ */
+export const cancelPostCliCall = async (callId: string): Promise<void> => {
+  await invoke('cancel_postcli_call', { callId });
+};
+
+/**
+ * Returns whether the backend exposes the generic postcli runner
+ *
+ * @returns {Promise<boolean>} True in debug builds or with SM_INIT_DEVELOPER_MODE set
+ */
+export const isDeveloperMode = async (): Promise<boolean> => {
+  return invoke<boolean>('is_developer_mode');
+};
+
+/**
+ * Lists the log files of postcli jobs, most recently written first
+ *
+ * @returns {Promise<JobLogInfo[]>} Job logs
+ */
+export const listJobLogs = async (): Promise<JobLogInfo[]> => {
+  return invoke<JobLogInfo[]>('list_job_logs');
+};
+
+/**
+ * Returns the last lines of a job log
+ *
+ * @param {string} name - Name of the job log
+ * @param {number} lines - Number of lines to return
+ * @returns {Promise<string[]>} Last lines, oldest first
+ */
+export const tailJobLog = async (name: string, lines: number): Promise<string[]> => {
+  return invoke<string[]>('tail_job_log', { name, lines });
+};
+
+/**
+ * Searches a job log by substring and/or minimum severity
+ *
+ * @param {string} name - Name of the job log
+ * @param {string} query - Optional case-insensitive substring
+ * @param {string} minSeverity - Optional lowest log level (DEBUG, INFO, WARN, ERROR, PANIC, FATAL)
+ * @param {number} limit - Optional maximum number of matches
+ * @returns {Promise<LogMatch[]>} Matching lines with their line numbers
+ */
+export const searchJobLog = async (
+  name: string,
+  query?: string,
+  minSeverity?: string,
+  limit?: number
+): Promise<LogMatch[]> => {
+  return invoke<LogMatch[]>('search_job_log', { name, query, minSeverity, limit });
+};
+
+/**
+ * Low-level function to execute postcli commands directly with a configuration
+ * Only available in developer mode, the UI uses the dedicated functions above
+ *
+ * @param {PostCliConfig} config - postcli configuration
+ * @param {Function} updateConsole - Optional callback for console updates
+ * @returns {Promise<PostCliResponse>} Command execution results
+ * @throws {Error} If developer mode is disabled, or validation or command execution fails
+ */
+export const callPostCli = async (
+  config: PostCliConfig,
+  updateConsole?: (command: string, output: string) => void
+): Promise<PostCliResponse> => {
+  console.log('callPostCli called with config:', config);
+  return invokeSyncPostCli(
+    'run_postcli_command',
+    { config },
+    formatPostCliCommand(config),
+    updateConsole
+  );
+};
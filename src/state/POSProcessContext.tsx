@@ -4,16 +4,16 @@
  * Provides a centralized way to manage and monitor long-running POS processes.
  */
 
+import { listen } from "@tauri-apps/api/event";
 import React, { createContext, ReactNode, useContext, useState } from "react";
 
-import { stopPostCliProcess } from "../services/postcliService";
+import { setPostCliJobPaused, stopPostCliProcess } from "../services/postcliService";
 import { SizeConstants } from "../Shared/Constants";
-import { Stage, FileProgress, POSSettings } from "../types/posProgress";
-import { parsePOSProgress } from "../utils/posProgressParser";
+import { Stage, FileProgress, POSSettings, DiskProgress, JobExit, JobStateChange, ProgressEvent } from "../types/posProgress";
+import { progressFromEvent } from "../utils/posProgressEvents";
 
 import { useConsole } from "./ConsoleContext";
 import { useSettings } from "./SettingsContext";
-import useFileProgress from '../hooks/useFileProgress';
 
 /**
  * State interface for POS process
@@ -26,8 +26,10 @@ interface POSProcessState {
   isError: boolean;
   logs: string[];
   fileProgress?: FileProgress;
+  diskProgress?: DiskProgress;
   isRunning: boolean;
-  processId: number | null;
+  isPaused: boolean;
+  jobId: number | null;
 }
 
 /**
@@ -36,8 +38,9 @@ interface POSProcessState {
  */
 interface POSProcessContextProps {
   processState: POSProcessState;
-  startProcess: (pid: number) => void;
+  startProcess: (jobId: number) => void;
   stopProcess: () => Promise<void>;
+  setPaused: (pause: boolean) => Promise<void>;
   processLog: (log: string) => void;
   reset: () => void;
 }
@@ -53,7 +56,8 @@ const initialState: POSProcessState = {
   isError: false,
   logs: [],
   isRunning: false,
-  processId: null,
+  isPaused: false,
+  jobId: null,
 };
 
 // Create context with undefined default value
@@ -80,23 +84,18 @@ export const POSProcessProvider: React.FC<{ children: ReactNode }> = ({
   const [processState, setProcessState] = useState<POSProcessState>(initialState);
   const { updateConsole } = useConsole();
   const { settings } = useSettings();
-  const { setFileIndex, fileProgress } = useFileProgress(
-    settings.selectedDir || settings.defaultDir || '',
-    (settings.maxFileSize ?? 32) * 1024 * 1024,
-    10000
-  );
 
   /**
    * Initializes a new POS process
    * Resets state and sets up for new process execution
-   * @param {number} pid - Process ID of the new process
+   * @param {number} jobId - Registry ID of the job running the new process
    */
-  const startProcess = (pid: number) => {
-    setFileIndex(0);
+  const startProcess = (jobId: number) => {
     setProcessState(prev => ({
       ...prev,
       isRunning: true,
-      processId: pid,
+      isPaused: false,
+      jobId,
       stage: Stage.Processing,
       progress: 0,
       details: "Starting POS data generation...",
@@ -111,13 +110,13 @@ export const POSProcessProvider: React.FC<{ children: ReactNode }> = ({
    * @throws {Error} If process termination fails
    */
   const stopProcess = async () => {
-    if (processState.processId) {
+    if (processState.jobId) {
       try {
-        await stopPostCliProcess(processState.processId, updateConsole);
+        await stopPostCliProcess(processState.jobId, updateConsole);
         setProcessState(prev => ({
           ...prev,
           isRunning: false,
-          processId: null,
+          jobId: null,
           stage: Stage.NotStarted,
           progress: 0,
           details: "Process stopped",
@@ -127,7 +126,7 @@ export const POSProcessProvider: React.FC<{ children: ReactNode }> = ({
         setProcessState(prev => ({
           ...prev,
           isRunning: false,
-          processId: null,
+          jobId: null,
           stage: Stage.Error,
           details:
             error instanceof Error ? error.message : "Failed to stop process",
@@ -139,66 +138,134 @@ export const POSProcessProvider: React.FC<{ children: ReactNode }> = ({
   };
 
   /**
-   * Processes a new log entry from the POS process
-   * Updates state based on parsed log content
-   * 
-   * Process:
-   * 1. Cleans log entry
-   * 2. Parses progress information
-   * 3. Updates state while preserving relevant previous state
-   * 4. Handles terminal states (Complete/Error)
-   * 
-   * @param {string} log - Raw log entry to process
+   * Pauses or resumes the currently running process
+   * The paused flag itself is updated from the backend's 'postcli-state' event
+   * @param {boolean} pause - Whether to pause (true) or resume (false)
+   */
+  const setPaused = async (pause: boolean) => {
+    if (processState.jobId) {
+      await setPostCliJobPaused(processState.jobId, pause, updateConsole);
+    }
+  };
+
+  /**
+   * Records a new log entry from the POS process
+   * Stage and errors come from the typed 'postcli-progress' events, not from the log text
+   * @param {string} log - Raw log entry to record
    */
   const processLog = React.useCallback((log: string) => {
-    console.log('Processing log:', log);
-    
     // Strip postcli stdout/stderr prefixes from the log
-    const cleanLog = log.replace(/^postcli (stdout|stderr):\s*/, '');
-    
-    // Prepare settings for progress parsing
-    const posSettings: POSSettings = {
-      numUnits: settings.numUnits || SizeConstants.DEFAULT_NUM_UNITS,
-      maxFileSize: settings.maxFileSize || SizeConstants.DEFAULT_MAX_FILE_SIZE_MIB
-    };
-    const parsed = parsePOSProgress(cleanLog, posSettings);
-    setProcessState(prev => {
-      // If we're already in a terminal state (Complete or Error), don't process more logs
-      if (prev.stage === Stage.Complete || (prev.stage === Stage.Error && prev.isError)) {
-        return prev;
-      }
+    const cleanLog = log.replace(/^(postcli )?(stdout|stderr):\s*/, '');
+    setProcessState(prev => ({ ...prev, logs: [...prev.logs, cleanLog] }));
+  }, []);
 
-      // Keep previous fileProgress if the new parsed state doesn't have it
-      const updatedFileProgress = parsed.fileProgress || prev.fileProgress;
-;
-      if (updatedFileProgress?.currentFile && !updatedFileProgress?.isCompleted) {
-        setFileIndex(updatedFileProgress.currentFile); 
-      }
-      const newState = {
-        ...prev,
-        stage: parsed.stage,
-        progress: parsed.progress,
-        details: parsed.details,
-        isError: parsed.isError,
-        fileProgress: updatedFileProgress,
-        logs: [...prev.logs, cleanLog],
-        // Update running state based on terminal conditions
-        isRunning: parsed.stage !== Stage.Complete && parsed.stage !== Stage.Error
+  // Track stage changes and errors parsed by the backend from the postcli log
+  React.useEffect(() => {
+    const unlisten = listen<ProgressEvent>('postcli-progress', (event) => {
+      const { job_id } = event.payload;
+      // Prepare settings for the file count
+      const posSettings: POSSettings = {
+        numUnits: settings.numUnits || SizeConstants.DEFAULT_NUM_UNITS,
+        maxFileSize: settings.maxFileSize || SizeConstants.DEFAULT_MAX_FILE_SIZE_MIB
       };
-      console.log('New process state:', newState);
-      return newState;
+      const parsed = progressFromEvent(event.payload.event, posSettings);
+      if (!parsed) {
+        return;
+      }
+      setProcessState(prev => {
+        // Ignore other jobs and events arriving after a terminal state (Complete or Error)
+        if (prev.jobId !== job_id || prev.stage === Stage.Complete || (prev.stage === Stage.Error && prev.isError)) {
+          return prev;
+        }
+        return {
+          ...prev,
+          stage: parsed.stage,
+          // While processing, progress comes from the data directory monitor
+          progress: parsed.stage === Stage.Processing ? prev.progress : parsed.progress,
+          details: parsed.details,
+          isError: parsed.isError,
+          // Keep previous fileProgress if the event doesn't have it
+          fileProgress: parsed.fileProgress || prev.fileProgress,
+          // Update running state based on terminal conditions
+          isRunning: parsed.stage !== Stage.Complete && parsed.stage !== Stage.Error
+        };
+      });
     });
-  }, [settings, setFileIndex]);
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn());
+    };
+  }, [settings]);
 
-  const totalFiles = processState.fileProgress?.totalFiles ?? 1;
-  const entireFileProgress = ((processState.fileProgress?.currentFile ?? 0) / totalFiles) * 100;
+  // Track progress measured by the backend on the data directory
   React.useEffect(() => {
-    const fileProgressPart = fileProgress / totalFiles;
-    setProcessState(prev => ({
-      ...prev,
-      progress: entireFileProgress + fileProgressPart,
-    }));
-  }, [entireFileProgress, fileProgress, totalFiles]);
+    const unlisten = listen<DiskProgress>('postcli-disk-progress', (event) => {
+      const diskProgress = event.payload;
+      setProcessState(prev => {
+        if (prev.jobId !== diskProgress.job_id || prev.stage !== Stage.Processing) {
+          return prev;
+        }
+        return {
+          ...prev,
+          progress: diskProgress.percentage,
+          diskProgress,
+        };
+      });
+    });
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn());
+    };
+  }, []);
+
+  // Track paused/resumed state changes reported by the backend
+  React.useEffect(() => {
+    const unlisten = listen<JobStateChange>('postcli-state', (event) => {
+      const { job_id, status } = event.payload;
+      setProcessState(prev =>
+        prev.jobId === job_id
+          ? { ...prev, isPaused: status.state === 'paused' }
+          : prev
+      );
+    });
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn());
+    };
+  }, []);
+
+  // Settle the final state once the job's process has exited
+  React.useEffect(() => {
+    const unlisten = listen<JobExit>('postcli-exit', (event) => {
+      const { job_id, code, signal, stderr_tail } = event.payload;
+      setProcessState(prev => {
+        if (prev.jobId !== job_id || !prev.isRunning) {
+          return prev;
+        }
+        if (code === 0) {
+          return {
+            ...prev,
+            stage: Stage.Complete,
+            progress: 100,
+            details: "POS data generation completed",
+            isError: false,
+            isRunning: false,
+            isPaused: false,
+          };
+        }
+        const reason = signal !== null ? `was killed by signal ${signal}` : `exited with code ${code ?? 'unknown'}`;
+        const lastError = stderr_tail[stderr_tail.length - 1];
+        return {
+          ...prev,
+          stage: Stage.Error,
+          details: `postcli ${reason}${lastError ? `: ${lastError}` : ''}`,
+          isError: true,
+          isRunning: false,
+          isPaused: false,
+        };
+      });
+    });
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn());
+    };
+  }, []);
 
   /**
    * Resets process state to initial values
@@ -208,18 +275,18 @@ export const POSProcessProvider: React.FC<{ children: ReactNode }> = ({
     setProcessState(initialState);
   };
 
-  // Set up event listener for progress updates
+  // Record the log lines forwarded by the postcli service
   React.useEffect(() => {
-    const handleProgress = (event: Event) => {
+    const handleLogLine = (event: Event) => {
       const customEvent = event as CustomEvent;
       if (customEvent.detail) {
         processLog(customEvent.detail);
       }
     };
 
-    window.addEventListener('postcli-progress', handleProgress);
-    return () => window.removeEventListener('postcli-progress', handleProgress);
-  }, [processLog]); // processLog contains settings dependency internally
+    window.addEventListener('postcli-log-line', handleLogLine);
+    return () => window.removeEventListener('postcli-log-line', handleLogLine);
+  }, [processLog]);
 
   return (
     <POSProcessContext.Provider
@@ -227,6 +294,7 @@ export const POSProcessProvider: React.FC<{ children: ReactNode }> = ({
         processState,
         startProcess,
         stopProcess,
+        setPaused,
         processLog,
         reset,
       }}
@@ -1,14 +1,23 @@
-type Base64String = string;
 type HexString = string;
 
-export type PosMetadata = {
-  NodeId: Base64String
-  CommitmentAtxId: Base64String;
-  LabelsPerUnit: number;
-  NumUnits: number;
-  MaxFileSize: number;
-  Nonce: number;
-  NonceValue: HexString;
+export type MetadataProblem = {
+  /** labels_per_unit_mismatch, missing_files, extra_files or init_not_finished */
+  kind: string;
+  message: string;
+};
+
+/** Decoded postdata_metadata.json as returned by the read_pos_metadata command */
+export type PosMetadataReport = {
+  node_id: HexString;
+  commitment_atx_id: HexString;
+  labels_per_unit: number;
+  num_units: number;
+  max_file_size: number;
+  nonce: number | null;
+  nonce_value: HexString | null;
+  expected_files: number;
+  found_files: number;
+  problems: MetadataProblem[];
 };
 
 export type ParsedMetadata = {
@@ -13,6 +13,63 @@ export interface FileProgress {
   totalFiles: number;
 }
 
+/**
+ * Progress measured by the backend on the data directory
+ * (payload of the 'postcli-disk-progress' event)
+ */
+export interface DiskProgress {
+  job_id: number;
+  bytes_done: number;
+  total_bytes: number;
+  percentage: number;
+  current_file: number | null;
+  total_files: number;
+  write_rate: number;
+  average_rate: number;
+  eta_secs: number | null;
+}
+
+/**
+ * Job state change reported by the backend
+ * (payload of the 'postcli-state' event)
+ */
+export interface JobStateChange {
+  job_id: number;
+  status: { state: 'running' | 'paused' | 'exited' };
+}
+
+/**
+ * Exit report of a job's process
+ * (payload of the 'postcli-exit' event)
+ */
+export interface JobExit {
+  job_id: number;
+  process_id: number;
+  code: number | null;
+  signal: number | null;
+  runtime_secs: number;
+  stderr_tail: string[];
+}
+
+/**
+ * Typed event parsed by the backend from a postcli log line
+ */
+export type PostcliEvent =
+  | { type: 'file_started'; file_index: number; resumed: boolean }
+  | { type: 'file_completed'; file_index: number; already_initialized: boolean }
+  | { type: 'nonce_found'; nonce: number | null }
+  | { type: 'completed' }
+  | { type: 'warning'; message: string }
+  | { type: 'fatal'; message: string; cause: string | null };
+
+/**
+ * Payload of the 'postcli-progress' event
+ */
+export interface ProgressEvent {
+  job_id: number;
+  event: PostcliEvent;
+}
+
 export interface POSSettings {
   numUnits: number;
   maxFileSize: number;
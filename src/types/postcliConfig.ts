@@ -0,0 +1,41 @@
+/**
+ * Typed postcli configuration validated and rendered to arguments by the backend
+ * Fields left out take their postcli default
+ */
+export interface PostCliConfig {
+  /** Only list the available OpenCL providers, all other fields are ignored */
+  print_providers?: boolean;
+  /** Data directory, postcli's ~/post/data if omitted */
+  datadir?: string | null;
+  num_units?: number;
+  provider?: number;
+  /** Commitment ATX ID as a hex string */
+  commitment_atx_id?: string;
+  /** Node identity as a hex string, generated by postcli if omitted */
+  id?: string | null;
+  /** Maximum size of a single data file in bytes */
+  max_file_size?: number | null;
+  labels_per_unit?: number | null;
+  /** First file to initialize */
+  from_file?: number | null;
+  /** Last file to initialize, inclusive */
+  to_file?: number | null;
+  /** Generate a proof after the initialization */
+  genproof?: boolean;
+  log_level?: string | null;
+}
+
+/**
+ * Problem with a single configuration field reported by the backend
+ */
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+/**
+ * Error returned by backend commands taking a PostCliConfig
+ */
+export type PostCliError =
+  | { kind: 'validation'; errors: FieldError[] }
+  | { kind: 'failed'; message: string };
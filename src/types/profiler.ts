@@ -24,6 +24,7 @@ export interface ProfilerConfig {
   data_size: number;    // Size of data to process in gibibytes
   duration: number;     // Duration of the benchmark in seconds
   data_file?: string;  // Optional path to the data file used for profiling
+  read_only?: boolean; // Only read an existing data file, not enforced by the profiler binary
 }
 
 /**
@@ -46,6 +47,8 @@ export interface Benchmark extends ProfilerResult {
   threads: number;        // Number of CPU threads to use
   status: BenchmarkStatus; // Current status of the benchmark
   data_file?: string;     // Path to the data file used in this benchmark
+  elapsed_s?: number;     // Time since the run started in seconds, updated while running
+  cached_at?: number;     // Recording time of a reused history result, seconds since the Unix epoch
 }
 
 /**
@@ -65,3 +68,156 @@ export interface AccuracyConfig {
   data_size: number;  // Size of data to use in accuracy testing (in GB)
   duration: number;   // Duration of the accuracy test in seconds
 }
+
+/**
+ * Inclusive range of values swept by the profiler
+ * @interface SweepRange
+ */
+export interface SweepRange {
+  start: number;  // First value of the range
+  end: number;    // Last value of the range (inclusive)
+  step: number;   // Increment between two values (nonces: multiple of 16)
+}
+
+/**
+ * Result of a single profiler point as returned by the backend
+ * @interface ProfilerPointResult
+ */
+export interface ProfilerPointResult {
+  nonces: number;             // Number of nonces of the point
+  threads: number;            // Number of threads of the point
+  time_s: number;             // Measured time in seconds
+  speed_gib_s: number;        // Processing speed in GiB/s
+  data_size: number;          // Size of the data file in GiB
+  duration: number;           // Duration of the run in seconds
+  data_file: string | null;   // Path to the data file used
+  read_only: boolean;         // Whether an existing data file was only read
+}
+
+/**
+ * Payload of the 'profiler-sweep-progress' event
+ * @interface SweepProgress
+ */
+export interface SweepProgress {
+  completed: number;             // Number of points measured so far
+  total: number;                 // Number of points of the sweep
+  result: ProfilerPointResult;   // Result of the point that just finished
+}
+
+/**
+ * Payload of the 'profiler-progress' event, sent every second and for every line the profiler prints
+ * @interface ProfilerProgress
+ */
+export interface ProfilerProgress {
+  run_id: string;  // Id the run was started with, or the one generated for it
+  nonces: number;         // Number of nonces of the running point
+  threads: number;        // Number of threads of the running point
+  elapsed_s: number;      // Time since the profiler was started in seconds
+  line: string | null;    // Line printed by the profiler, null for elapsed-time updates
+}
+
+/**
+ * Results of a profiler sweep
+ * @interface ProfilerSweepResult
+ */
+export interface ProfilerSweepResult {
+  results: ProfilerPointResult[];  // One result per (nonces, threads) point
+  fastest: ProfilerPointResult[];  // Fastest point for every nonce count
+}
+
+/**
+ * Input of the proving feasibility estimate; at least one of the speeds is required
+ * @interface FeasibilityInput
+ */
+export interface FeasibilityInput {
+  num_units: number;              // Number of space units of the PoS data
+  nonces: number;                 // Number of nonces used for proving
+  cpu_speed_gib_s?: number;       // Speed measured by the profiler in GiB/s, ignored if not positive
+  disk_read_mib_s?: number;       // Read speed of the data disk in MiB/s, ignored if not positive
+  cycle_gap_hours?: number;       // Cycle gap in hours, SizeConstants.CYCLE_GAP_HOURS if omitted
+  safe_period?: number;           // Share of the cycle gap available for proving (0-1], SizeConstants.K_SAFE_PERIOD if omitted
+  target_probability?: number;    // Probability required for a feasible verdict (0-1), 0.99 if omitted
+}
+
+/**
+ * Estimated proving time and whether it fits inside the safe part of the cycle gap
+ * @interface FeasibilityEstimate
+ */
+export interface FeasibilityEstimate {
+  verdict: 'feasible' | 'risky' | 'infeasible';
+  limiting_factor: 'cpu' | 'disk';   // Whether the CPU or the disk limits a pass
+  data_size: number;                 // Size of the PoS data in bytes
+  pass_time_s: number;               // Duration of one pass in seconds
+  pass_probability: number;          // Probability that one pass yields a proof
+  expected_passes: number;           // Expected number of passes until a proof is found
+  expected_total_s: number;          // Expected proving time in seconds
+  deadline_s: number;                // Time available for proving in seconds
+  passes_in_deadline: number;        // Complete passes that fit before the deadline
+  probability_in_deadline: number;   // Probability of finding a proof before the deadline
+}
+
+/**
+ * Options of the proving configuration search
+ * @interface OptimizerOptions
+ */
+export interface OptimizerOptions {
+  reserved_cores_share?: number;  // Share of CPU cores to keep free, 0.25 if omitted
+  nonces?: SweepRange;            // Nonce counts to measure, multiples of 16
+  disk_read_mib_s?: number;       // Read speed of the data disk in MiB/s, ignored if not positive
+}
+
+/**
+ * Measured point with its proving estimate
+ * @interface ProvingCandidate
+ */
+export interface ProvingCandidate {
+  result: ProfilerPointResult;
+  estimate: FeasibilityEstimate;
+}
+
+/**
+ * Result of the proving configuration search
+ * @interface ProvingRecommendation
+ */
+export interface ProvingRecommendation {
+  cores: number;                     // Logical CPU cores of the machine
+  reserved_cores: number;            // Cores kept free
+  candidates: ProvingCandidate[];    // All measured points
+  recommended: ProvingCandidate;     // Point most likely to prove in time
+}
+
+/**
+ * Hardware a profiler result was measured on
+ * @interface HardwareFingerprint
+ */
+export interface HardwareFingerprint {
+  cpu_model: string;      // CPU model name
+  cores: number;          // Logical CPU cores
+  memory_mib: number;     // Total memory in MiB
+  os: string;             // Operating system and release
+  data_location: string;  // Directory holding the profiler data file
+}
+
+/**
+ * Profiler run stored in the history
+ * @interface ProfilerHistoryEntry
+ */
+export interface ProfilerHistoryEntry {
+  id: string;                        // Identifier of the entry
+  recorded_at: number;               // Recording time in seconds since the Unix epoch
+  fingerprint: HardwareFingerprint;  // Hardware the result was measured on
+  result: ProfilerPointResult;       // Measured result
+}
+
+/**
+ * Criteria for listing or exporting the profiler history; unset fields match every entry
+ * @interface HistoryFilter
+ */
+export interface HistoryFilter {
+  nonces?: number;
+  threads?: number;
+  fingerprint?: HardwareFingerprint;  // Only entries measured on exactly this hardware
+  cpu_model?: string;
+  since?: number;                     // Seconds since the Unix epoch
+  until?: number;                     // Seconds since the Unix epoch
+}
@@ -0,0 +1,77 @@
+import { Stage } from '../../types/posProgress';
+import { progressFromEvent } from '../posProgressEvents';
+import { calculateNumFiles } from '../sizeUtils';
+
+describe('progressFromEvent', () => {
+  const mockSettings = {
+    numUnits: 100,
+    maxFileSize: 1000
+  };
+  const totalFiles = calculateNumFiles(mockSettings.numUnits, mockSettings.maxFileSize);
+
+  it('reports the file being written', () => {
+    const result = progressFromEvent({ type: 'file_started', file_index: 3, resumed: true }, mockSettings);
+
+    expect(result).toEqual({
+      stage: Stage.Processing,
+      progress: (3 / totalFiles) * 100,
+      details: `Continuing file 4 of ${totalFiles}...`,
+      isError: false,
+      fileProgress: {
+        isCompleted: false,
+        currentFile: 3,
+        totalFiles
+      }
+    });
+  });
+
+  it('reports completed files', () => {
+    const result = progressFromEvent(
+      { type: 'file_completed', file_index: 5, already_initialized: false },
+      mockSettings
+    );
+    const progress = ((5 + 1) / totalFiles) * 100;
+
+    expect(result).toEqual({
+      stage: Stage.Processing,
+      progress,
+      details: `6 of ${totalFiles} files generated (${Math.round(progress)}%)`,
+      isError: false,
+      fileProgress: {
+        isCompleted: true,
+        currentFile: 5,
+        totalFiles
+      }
+    });
+  });
+
+  it('reports fatal errors with their cause', () => {
+    const result = progressFromEvent(
+      { type: 'fatal', message: 'ERROR: Failed to write file', cause: 'disk full' },
+      mockSettings
+    );
+
+    expect(result).toEqual({
+      stage: Stage.Error,
+      progress: 0,
+      details: 'disk full (ERROR: Failed to write file)',
+      isError: true
+    });
+  });
+
+  it('reports final completion', () => {
+    const result = progressFromEvent({ type: 'completed' }, mockSettings);
+
+    expect(result).toEqual({
+      stage: Stage.Complete,
+      progress: 100,
+      details: `All ${totalFiles} files have been generated successfully`,
+      isError: false
+    });
+  });
+
+  it('ignores warnings and found nonces', () => {
+    expect(progressFromEvent({ type: 'warning', message: 'WARN slow disk' }, mockSettings)).toBeNull();
+    expect(progressFromEvent({ type: 'nonce_found', nonce: 7 }, mockSettings)).toBeNull();
+  });
+});
@@ -7,6 +7,8 @@
 import { homeDir, join } from '@tauri-apps/api/path';
 import { invoke } from "@tauri-apps/api/tauri";
 
+import { PostCliConfig } from '../types/postcliConfig';
+
 /**
  * Result of directory validation checks
  * @interface DirectoryValidationResult
@@ -14,6 +16,23 @@ import { invoke } from "@tauri-apps/api/tauri";
 export interface DirectoryValidationResult {
   isValid: boolean;
   error?: string;
+  /** Problems that do not prevent using the directory, e.g. an unsuited filesystem */
+  warnings?: string[];
+}
+
+/**
+ * Filesystem holding a directory
+ * @interface FilesystemInfo
+ */
+export interface FilesystemInfo {
+  /** Filesystem type as reported by the OS, e.g. ext4, vfat, apfs or NTFS */
+  fs_type: string;
+  mount_point: string | null;
+  device: string | null;
+  /** Whether the storage is a removable drive, null if unknown */
+  removable: boolean | null;
+  /** Whether the filesystem is backed by a network share */
+  network: boolean;
 }
 
 /**
@@ -26,9 +45,16 @@ interface BackendValidation {
   exists: boolean;
   /** Whether we have write permissions */
   has_write_permission: boolean;
-  /** Whether there's enough space (minimum 1GB for now, 
-   * in a separate branch check-diskspace I tried to properly check the available space) */
+  /** Whether the filesystem holding the directory has the required space */
   has_space: boolean;
+  /** Free space on the filesystem holding the directory in bytes */
+  available_space: number | null;
+  /** Space the planned initialization still needs in bytes (1GB if not planned yet) */
+  required_space: number;
+  /** Filesystem holding the directory, null if it cannot be determined */
+  filesystem: FilesystemInfo | null;
+  /** Warnings about the filesystem, e.g. FAT32 or network shares */
+  warnings: string[];
   /** Error message if validation failed */
   error: string | null;
 }
@@ -38,12 +64,17 @@ interface BackendValidation {
  * Performs comprehensive checks including:
  * - Directory existence
  * - Write permissions
- * - Available space
+ * - Available space for the rest of the planned initialization
+ * - Filesystem suitability, reported as warnings
  * @param {string | null} path - Directory path to validate
+ * @param {number} numUnits - Planned number of space units, if already chosen
+ * @param {number} maxFileSize - Planned maximum file size in bytes, if already chosen
  * @returns {Promise<DirectoryValidationResult>} Validation result with any error messages
  */
 export const validateDirectory = async (
-  path: string | null
+  path: string | null,
+  numUnits?: number,
+  maxFileSize?: number
 ): Promise<DirectoryValidationResult> => {
   if (!path) {
     return {
@@ -54,7 +85,11 @@ export const validateDirectory = async (
 
   try {
     // Use the comprehensive backend validation
-    const validation = await invoke<BackendValidation>("verify_directory", { path });
+    const validation = await invoke<BackendValidation>("verify_directory", {
+      path,
+      numUnits,
+      maxFileSize,
+    });
 
     // If there's an error message from the backend, use it
     if (validation.error) {
@@ -82,13 +117,14 @@ export const validateDirectory = async (
     if (!validation.has_space) {
       return {
         isValid: false,
-        error: "Not enough space in the selected directory (minimum 1GB required)",
+        error: "Not enough space in the selected directory for the planned initialization",
       };
     }
 
     // All checks passed
     return {
       isValid: true,
+      warnings: validation.warnings,
     };
   } catch (err) {
     return {
@@ -98,6 +134,58 @@ export const validateDirectory = async (
   }
 };
 
+/**
+ * State of a single postdata_N.bin file compared with the expected layout
+ * @interface DataFileReport
+ */
+export interface DataFileReport {
+  index: number;
+  /** Size on disk, null if the file is missing */
+  size: number | null;
+  expected_size: number;
+  status: 'complete' | 'missing' | 'truncated' | 'oversized';
+}
+
+/**
+ * Detailed report of a PoS data directory
+ * @interface PosDirectoryReport
+ */
+export interface PosDirectoryReport {
+  /** Overall classification of the directory */
+  state: 'empty' | 'partial' | 'complete' | 'inconsistent';
+  /** Whether the expected layout comes from the metadata file or the given configuration */
+  layout_source: 'metadata' | 'config';
+  num_files: number;
+  files: DataFileReport[];
+  /** postdata_N.bin files beyond the last expected index */
+  extra_data_files: string[];
+  /** Any other files not written by postcli */
+  unexpected_files: string[];
+  has_metadata: boolean;
+  has_nonce: boolean;
+  metadata_error: string | null;
+  has_identity_key: boolean;
+  has_key_bin: boolean;
+  /** First file that is not complete, where resuming continues */
+  resume_from_file: number | null;
+  /** Bytes still to be written to complete the data */
+  remaining_bytes: number;
+}
+
+/**
+ * Inspects a PoS data directory file by file
+ * Tells whether the directory is empty, can be resumed, is complete or is inconsistent
+ * @param {string} dir - Directory path to inspect
+ * @param {PostCliConfig} config - Chosen configuration, used if the directory has no metadata
+ * @returns {Promise<PosDirectoryReport>} Report of the directory
+ */
+export const inspectPosDirectory = async (
+  dir: string,
+  config?: PostCliConfig
+): Promise<PosDirectoryReport> => {
+  return invoke<PosDirectoryReport>("inspect_pos_directory", { dir, config });
+};
+
 /**
  * Gets the default directory path for POS data
  * Creates path under user's home directory: ~/post/data
@@ -133,7 +221,7 @@ export const handleDirectoryError = (error: unknown): string => {
       return "Directory selection was cancelled";
     }
     if (error.message.includes("space")) {
-      return "Not enough space in the selected directory (minimum 1GB required)";
+      return "Not enough space in the selected directory for the planned initialization";
     }
     if (error.message.includes("disk")) {
       return "Failed to check disk space. Please ensure the drive is accessible";
@@ -149,17 +237,32 @@ export const handleDirectoryError = (error: unknown): string => {
  * Checks if a directory has sufficient space
  * Can be used independently of full validation
  * @param {string} path - Directory path to check
+ * @param {number} numUnits - Planned number of space units, if already chosen
+ * @param {number} maxFileSize - Planned maximum size of a data file in bytes, PostCLI's default if omitted
  * @returns {Promise<boolean>} Whether directory has enough space
  */
-export const checkDirectorySpace = async (path: string): Promise<boolean> => {
+export const checkDirectorySpace = async (
+  path: string,
+  numUnits?: number,
+  maxFileSize?: number
+): Promise<boolean> => {
   try {
-    return await invoke<boolean>("check_directory_space", { path });
+    return await invoke<boolean>("check_directory_space", { path, numUnits, maxFileSize });
   } catch (err) {
     console.error("Error checking directory space:", err);
     return false;
   }
 };
 
+/**
+ * Detects the filesystem holding a directory
+ * @param {string} path - Directory path to check
+ * @returns {Promise<FilesystemInfo>} Filesystem type, mount point, device and storage kind
+ */
+export const getFilesystemInfo = async (path: string): Promise<FilesystemInfo> => {
+  return invoke<FilesystemInfo>("get_filesystem_info", { path });
+};
+
 /**
  * Checks if we have write permission for a directory
  * Can be used independently of full validation
@@ -228,3 +331,77 @@ export const shortenPath = (path: string, maxLength: number): string => {
 
   return result.join("/");
 };
+
+/**
+ * Payload of the 'disk-benchmark-progress' event
+ * @interface DiskBenchmarkProgress
+ */
+export interface DiskBenchmarkProgress {
+  kind: 'write' | 'read';
+  bytes_done: number;
+  total_bytes: number;
+  /** Average throughput so far in MiB/s */
+  speed_mib_s: number;
+}
+
+/**
+ * Result of a sequential write benchmark
+ * @interface WriteBenchmarkResult
+ */
+export interface WriteBenchmarkResult {
+  bytes_written: number;
+  duration_s: number;
+  /** Sequential write throughput in MiB/s */
+  speed_mib_s: number;
+  /** Whether the page cache was bypassed */
+  direct_io: boolean;
+}
+
+/**
+ * Measures the sequential write throughput of the disk holding a directory
+ * Progress is reported through 'disk-benchmark-progress' events.
+ * Compare the result with the provider's speed to tell whether the disk limits the initialization.
+ * @param {string} dir - Directory to benchmark
+ * @param {number} sizeMib - Amount of data to write in MiB (default: 1024)
+ * @returns {Promise<WriteBenchmarkResult>} Write throughput
+ */
+export const benchmarkDirectoryWrite = async (
+  dir: string,
+  sizeMib?: number
+): Promise<WriteBenchmarkResult> => {
+  return invoke<WriteBenchmarkResult>("benchmark_directory_write", { dir, sizeMib });
+};
+
+/**
+ * Result of a read benchmark over existing PoS data
+ * @interface ReadBenchmarkResult
+ */
+export interface ReadBenchmarkResult {
+  bytes_read: number;
+  /** Number of data files a sample was read from */
+  files_sampled: number;
+  duration_s: number;
+  /** Sustained read throughput in MiB/s */
+  speed_mib_s: number;
+  /** Whether the page cache was bypassed */
+  direct_io: boolean;
+  /** Size of the PoS data set in bytes */
+  data_size: number;
+  /** Projected duration of one proving pass over the whole data set in seconds */
+  projected_pass_s: number;
+}
+
+/**
+ * Measures the read throughput of the PoS data in a directory
+ * Reads a sample of every postdata_N.bin file and projects the duration of a proving pass.
+ * Progress is reported through 'disk-benchmark-progress' events.
+ * @param {string} dir - PoS data directory
+ * @param {number} sampleMib - Amount of data to read in MiB (default: 2048)
+ * @returns {Promise<ReadBenchmarkResult>} Read throughput and projected proving pass duration
+ */
+export const benchmarkDirectoryRead = async (
+  dir: string,
+  sampleMib?: number
+): Promise<ReadBenchmarkResult> => {
+  return invoke<ReadBenchmarkResult>("benchmark_directory_read", { dir, sampleMib });
+};
@@ -5,7 +5,7 @@
 
 import React, { useCallback, useRef, useState } from "react";
 
-import { callPostCli } from "../services/postcliService";
+import { cancelPostCliCall, printPostCliProviders } from "../services/postcliService";
 
 /**
  * Represents a hardware provider (CPU/GPU) in the system
@@ -25,8 +25,8 @@ interface Provider {
  * @interface UsePostCliReturn
  */
 interface UsePostCliReturn {
-  /** Function to execute postcli command with arguments */
-  run: (args: string[], updateConsole?: (command: string, output: string) => void) => Promise<void>;
+  /** Function to detect the available providers */
+  run: (updateConsole?: (command: string, output: string) => void) => Promise<void>;
   /** Parsed provider information */
   response: Provider[] | null;
   /** Function to manually update provider information */
@@ -52,6 +52,7 @@ const FindProviders = (): UsePostCliReturn => {
   const [error, setError] = useState<string | null>(null);
   const [loading, setLoading] = useState<boolean>(false);
   const abortControllerRef = useRef<AbortController | null>(null);
+  const callIdRef = useRef<string | null>(null);
 
   /**
    * Parses raw postcli output to extract provider information
@@ -124,29 +125,33 @@ const FindProviders = (): UsePostCliReturn => {
   /**
    * Executes postcli command to detect providers
    * Handles:
-   * - Request cancellation for concurrent calls
+   * - Request cancellation for concurrent calls (the running postcli is killed)
    * - Error handling and validation
    * - Console output updates
    * 
-   * @param {string[]} args - Command arguments for postcli
    * @param {Function} updateConsole - Optional callback for console updates
    */
-  const run = useCallback(async (args: string[], updateConsole?: (command: string, output: string) => void): Promise<void> => {
+  const run = useCallback(async (updateConsole?: (command: string, output: string) => void): Promise<void> => {
     // Cancel any existing request
     if (abortControllerRef.current) {
       abortControllerRef.current.abort();
     }
+    if (callIdRef.current) {
+      cancelPostCliCall(callIdRef.current).catch(() => undefined);
+    }
 
-    // Create new abort controller for this request
-    abortControllerRef.current = new AbortController();
+    // Create new abort controller and backend call id for this request
+    const controller = new AbortController();
+    const callId = `print-providers-${Date.now()}`;
+    abortControllerRef.current = controller;
+    callIdRef.current = callId;
 
     setLoading(true);
     try {
-      // Pass only the supported arguments to callPostCli
-      const result = await callPostCli(args, updateConsole);
+      const result = await printPostCliProviders(updateConsole, callId);
       
-      if (!result.stdout && result.stderr) {
-        throw new Error(result.stderr);
+      if (!result.success) {
+        throw new Error(result.stderr || `postcli exited with code ${result.exit_code}`);
       }
       
       const parsedResult = parseResponse(result.stdout);
@@ -159,17 +164,19 @@ const FindProviders = (): UsePostCliReturn => {
       setError(null);
     } catch (err: any) {
       // Don't set error state if the request was intentionally cancelled
-      if (err.name !== 'AbortError') {
+      if (!controller.signal.aborted) {
         const errorMessage = err.message || 'Unknown error occurred while finding providers';
         console.error('Provider detection error:', errorMessage);
         setError(errorMessage);
         setResponse(null);
       }
     } finally {
-      if (abortControllerRef.current) {
+      // A newer request owns the loading state once this one was cancelled
+      if (abortControllerRef.current === controller) {
         abortControllerRef.current = null;
+        callIdRef.current = null;
+        setLoading(false);
       }
-      setLoading(false);
     }
   }, [parseResponse]);
 
@@ -0,0 +1,69 @@
+/**
+ * @fileoverview Maps typed postcli progress events to the POS process state
+ * The backend parses the postcli log ('postcli-progress' event, see
+ * src-tauri/src/commands/log_parser.rs), so the UI does not break when the log format changes.
+ * The progress percentage is measured by the backend on the data directory instead
+ * ('postcli-disk-progress' event, see src-tauri/src/commands/monitor.rs).
+ */
+
+import { Stage, ParsedPOSProgress, PostcliEvent, POSSettings } from "../types/posProgress";
+
+import { calculateNumFiles } from "./sizeUtils";
+
+/**
+ * Converts a typed postcli event into the stage and details shown to the user
+ *
+ * @param {PostcliEvent} event - Event parsed by the backend from a postcli log line
+ * @param {POSSettings} settings - Current POS configuration settings
+ * @returns {ParsedPOSProgress | null} Stage, details and file progress, or null for events that do not change the state
+ */
+export const progressFromEvent = (event: PostcliEvent, settings: POSSettings): ParsedPOSProgress | null => {
+  // Calculate expected total number of files based on current settings
+  const totalFiles = calculateNumFiles(settings.numUnits, settings.maxFileSize);
+
+  switch (event.type) {
+    case 'file_started':
+      return {
+        stage: Stage.Processing,
+        progress: (event.file_index / totalFiles) * 100,
+        details: `${event.resumed ? 'Continuing' : 'Writing'} file ${event.file_index + 1} of ${totalFiles}...`,
+        isError: false,
+        fileProgress: {
+          isCompleted: false,
+          currentFile: event.file_index,
+          totalFiles,
+        },
+      };
+    case 'file_completed': {
+      const progress = ((event.file_index + 1) / totalFiles) * 100;
+      return {
+        stage: Stage.Processing,
+        progress,
+        details: `${event.file_index + 1} of ${totalFiles} files generated (${Math.round(progress)}%)`,
+        isError: false,
+        fileProgress: {
+          isCompleted: true,
+          currentFile: event.file_index,
+          totalFiles,
+        },
+      };
+    }
+    case 'completed':
+      return {
+        stage: Stage.Complete,
+        progress: 100,
+        details: `All ${totalFiles} files have been generated successfully`,
+        isError: false,
+      };
+    case 'fatal':
+      return {
+        stage: Stage.Error,
+        progress: 0,
+        details: event.cause ? `${event.cause} (${event.message})` : event.message,
+        isError: true,
+      };
+    case 'nonce_found':
+    case 'warning':
+      return null;
+  }
+};
@@ -6,6 +6,7 @@
 
 import { SizeConstants } from "../Shared/Constants";
 import { Settings } from "../state/SettingsContext";
+import { PostCliConfig, PostCliError } from "../types/postcliConfig";
 
 import { isValidHex } from "./hexUtils";
 
@@ -13,18 +14,19 @@ import { isValidHex } from "./hexUtils";
 const MIB_TO_BYTES = 1048576;
 
 /**
- * Builds command line arguments for postcli based on provided settings
+ * Builds the postcli configuration sent to the backend from the provided settings
+ * The backend validates the configuration again and renders it to arguments itself
  * 
- * Argument Building Process:
+ * Configuration Building Process:
  * 1. Validates ATX ID availability and format
- * 2. Adds required arguments (provider, numUnits, commitmentAtxId)
- * 3. Adds optional arguments if provided (id, datadir, maxFileSize)
- * 4. Adds configuration flags (labelsPerUnit, yes)
+ * 2. Sets required fields (provider, numUnits, commitmentAtxId)
+ * 3. Sets optional fields if provided (id, datadir, maxFileSize)
+ * 4. Sets labelsPerUnit
  * 
  * @param {Settings} settings - Configuration settings for postcli
- * @returns {string[] | null} Array of command arguments or null if invalid/incomplete settings
+ * @returns {PostCliConfig | null} postcli configuration or null if invalid/incomplete settings
  */
-export const buildPostCliArgs = (settings: Settings): string[] | null => {
+export const buildPostCliConfig = (settings: Settings): PostCliConfig | null => {
   // If ATX ID is being fetched from API, wait for it
   if (!settings.atxId && settings.atxIdSource === 'api') {
     return null; // Return null to indicate postcli should wait
@@ -40,40 +42,74 @@ export const buildPostCliArgs = (settings: Settings): string[] | null => {
     return null;
   }
 
-  const args: string[] = [];
-
-  // Required arguments
-  args.push(`-provider=${settings.provider || 0}`);
-  args.push(`-numUnits=${settings.numUnits || SizeConstants.DEFAULT_NUM_UNITS}`);
-  args.push(`-commitmentAtxId=${settings.atxId}`); // ATX ID is required
+  return {
+    provider: settings.provider || 0,
+    num_units: settings.numUnits || SizeConstants.DEFAULT_NUM_UNITS,
+    commitment_atx_id: settings.atxId,
+    // Optional identity - only pass it if publicKey is provided and valid
+    id: settings.publicKey && isValidHex(settings.publicKey, 64) ? settings.publicKey : null,
+    // Directory - only set if custom directory is provided
+    datadir: settings.selectedDir && settings.selectedDir.trim() !== "" ? settings.selectedDir : null,
+    // Convert maxFileSize from MiB to bytes
+    max_file_size: settings.maxFileSize ? settings.maxFileSize * MIB_TO_BYTES : null,
+    // Temporary setting for quicker testing
+    labels_per_unit: SizeConstants.DEFAULT_LABELS_PER_UNIT,
+  };
+};
 
-  // Optional identity - only pass -id flag if publicKey is provided and valid
-  if (settings.publicKey && isValidHex(settings.publicKey, 64)) {
-    args.push(`-id=${settings.publicKey}`);
+/**
+ * Renders a postcli configuration as a command line for console output
+ * Mirrors the arguments rendered by the backend
+ *
+ * @param {PostCliConfig} config - postcli configuration
+ * @returns {string} Command line shown to the user
+ */
+export const formatPostCliCommand = (config: PostCliConfig): string => {
+  if (config.print_providers) {
+    return './postcli -printProviders';
   }
 
-  // Directory - only add if custom directory is provided
-  if (settings.selectedDir && settings.selectedDir.trim() !== "") {
-    args.push(`-datadir=${settings.selectedDir}`);
-  }
+  const args: string[] = [
+    `-provider=${config.provider ?? 0}`,
+    `-numUnits=${config.num_units ?? SizeConstants.DEFAULT_NUM_UNITS}`,
+    `-commitmentAtxId=${config.commitment_atx_id ?? ''}`,
+  ];
+  if (config.id) args.push(`-id=${config.id}`);
+  if (config.datadir) args.push(`-datadir=${config.datadir}`);
+  if (config.max_file_size) args.push(`-maxFileSize=${config.max_file_size}`);
+  if (config.labels_per_unit) args.push(`-labelsPerUnit=${config.labels_per_unit}`);
+  if (config.from_file != null) args.push(`-fromFile=${config.from_file}`);
+  if (config.to_file != null) args.push(`-toFile=${config.to_file}`);
+  if (config.log_level) args.push(`-logLevel=${config.log_level}`);
+  if (config.genproof) args.push('-genproof');
+  args.push('-yes');
+
+  return `./postcli ${args.join(' ')}`;
+};
 
-  // Add maxFileSize if provided, converting from MiB to bytes
-  if (settings.maxFileSize) {
-    const maxFileSizeBytes = settings.maxFileSize * MIB_TO_BYTES;
-    args.push(`-maxFileSize=${maxFileSizeBytes}`);
+/**
+ * Converts an error returned by a postcli backend command into a readable message
+ * Validation errors list every rejected field
+ *
+ * @param {unknown} error - Error thrown by invoke
+ * @returns {string} Error message
+ */
+export const formatPostCliError = (error: unknown): string => {
+  if (error instanceof Error) {
+    return error.message;
   }
-
-  // Add debug log level
-  //args.push("-logLevel=debug");
-
-  // Temporary flag for quicker testing
-  args.push(`-labelsPerUnit=${SizeConstants.DEFAULT_LABELS_PER_UNIT}`);
-  args.push("-yes");
-
-  // Always add genproof
-  //args.push("-genproof");
-
-  return args;
+  if (typeof error === 'string') {
+    return error;
+  }
+  const postCliError = error as PostCliError | undefined;
+  if (postCliError?.kind === 'validation') {
+    const fields = postCliError.errors.map((e) => `${e.field} ${e.message}`);
+    return `Invalid postcli configuration: ${fields.join('; ')}`;
+  }
+  if (postCliError?.kind === 'failed') {
+    return postCliError.message;
+  }
+  return 'An unknown error occurred';
 };
 
 /**
@@ -4,7 +4,10 @@
  * and calculate storage requirements based on system parameters.
  */
 
+import { invoke } from "@tauri-apps/api/tauri";
+
 import { SizeConstants } from "../Shared/Constants";
+import { FeasibilityEstimate, FeasibilityInput } from "../types/profiler";
 
 /**
  * Calculates the size of a single unit in bytes based on the number of labels per unit
@@ -48,6 +51,24 @@ export const calculateMaxDataSize = (speed: number): number => {
   return Math.floor(maxSafeSize);
 };
 
+/**
+ * Estimates whether proving finishes within the safe part of the cycle gap
+ * Accounts for the number of passes needed and the slower of CPU and disk
+ * The cycle gap and safety period default to SizeConstants, as in calculateMaxDataSize
+ * @param {FeasibilityInput} input - Data size, nonces and measured speeds
+ * @returns {Promise<FeasibilityEstimate>} Expected proving time, verdict and limiting factor
+ */
+export const estimateProvingFeasibility = async (
+  input: FeasibilityInput
+): Promise<FeasibilityEstimate> =>
+  invoke<FeasibilityEstimate>("estimate_proving_feasibility", {
+    input: {
+      cycle_gap_hours: SizeConstants.CYCLE_GAP_HOURS,
+      safe_period: SizeConstants.K_SAFE_PERIOD,
+      ...input,
+    },
+  });
+
 /**
  * Formats a size in GiB to a human-readable string with appropriate unit
  * Automatically scales to larger units (TiB, PiB) when size is large enough